@@ -0,0 +1,101 @@
+use crate::error::AocError;
+use crate::registry::{Day, Part};
+use crate::solution::Answer;
+use std::time::Duration;
+
+/// Timings of repeatedly parsing and solving one part of a day.
+#[derive(Clone, Debug)]
+pub struct Benchmark {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Answer,
+    /// `None` for days timed as a whole, whose solve times include parsing.
+    pub parse: Option<Statistics>,
+    pub solve: Statistics,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Statistics {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Statistics {
+    /// Summarizes a non-empty list of measurements.
+    pub fn of(durations: &[Duration]) -> Self {
+        let mut durations = durations.to_vec();
+        durations.sort();
+
+        Statistics {
+            min: durations[0],
+            median: durations[durations.len() / 2],
+            max: durations[durations.len() - 1],
+        }
+    }
+}
+
+/// Parses and solves `part` of `day` `runs` times, which has to be at least once.
+pub fn bench(day: &Day, part: Part, input: &str, runs: usize) -> Result<Benchmark, AocError> {
+    assert!(runs > 0, "at least one run is required");
+
+    let mut parse_times = Vec::with_capacity(runs);
+    let mut solve_times = Vec::with_capacity(runs);
+    let mut answer = None;
+    for _ in 0..runs {
+        let run = day.run(input, part)?;
+        parse_times.push(run.parse_time);
+        solve_times.push(run.solve_time);
+        answer = Some(run.answer);
+    }
+
+    Ok(Benchmark {
+        day: day.number,
+        part,
+        answer: answer.unwrap(),
+        parse: parse_times.into_iter().collect::<Option<Vec<_>>>().map(|parse_times| Statistics::of(&parse_times)),
+        solve: Statistics::of(&solve_times),
+    })
+}
+
+/// Formats a duration with two decimals in the largest unit that keeps it above one.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+    match nanos {
+        nanos if nanos >= 1e9 => format!("{:.2}s", nanos / 1e9),
+        nanos if nanos >= 1e6 => format!("{:.2}ms", nanos / 1e6),
+        nanos if nanos >= 1e3 => format!("{:.2}µs", nanos / 1e3),
+        nanos => format!("{}ns", nanos),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn statistics() {
+        let durations = [5, 1, 4, 2, 3].map(Duration::from_millis);
+        assert_eq!(
+            Statistics { min: Duration::from_millis(1), median: Duration::from_millis(3), max: Duration::from_millis(5) },
+            Statistics::of(&durations)
+        );
+    }
+
+    #[test]
+    fn bench_day() {
+        let benchmark = bench(registry::find(11).unwrap(), Part::One, "125 17", 3).unwrap();
+        assert_eq!(Answer::Unsigned(55312), benchmark.answer);
+        assert!(benchmark.solve.min <= benchmark.solve.median && benchmark.solve.median <= benchmark.solve.max);
+        assert!(bench(registry::find(11).unwrap(), Part::One, "125 x", 3).is_err());
+    }
+
+    #[test]
+    fn format_durations() {
+        assert_eq!("1.50s", format_duration(Duration::from_millis(1500)));
+        assert_eq!("2.25ms", format_duration(Duration::from_micros(2250)));
+        assert_eq!("12.00µs", format_duration(Duration::from_micros(12)));
+        assert_eq!("999ns", format_duration(Duration::from_nanos(999)));
+    }
+}
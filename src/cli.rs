@@ -0,0 +1,727 @@
+use aoc2024::bench::{self, Benchmark, Statistics};
+use aoc2024::client::{self, UreqClient};
+use aoc2024::fetch::{self, Fetched};
+use aoc2024::input::{self, InputSource};
+use aoc2024::random::Rng;
+use aoc2024::registry::{self, Part};
+use aoc2024::runner::{self, Outcome, Report};
+use aoc2024::scaffold;
+use aoc2024::submit::{self, Submission, Verdict};
+use aoc2024::verify::{self, Status, Verification};
+use crate::output::{Format, Records};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+pub const USAGE: &str = "Usage:
+    aoc2024 run --day <DAY> [--part <PART>] [--input <FILE>|-] [--inputs-dir <DIR>] [--timeout <SECS>] [--format <FORMAT>]
+    aoc2024 run --all [--inputs-dir <DIR>] [--jobs <JOBS>] [--timeout <SECS>] [--format <FORMAT>]
+    aoc2024 run --day <DAY> [--part <PART>] [--input <FILE>|-] [--inputs-dir <DIR>] --stream
+    aoc2024 bench (--day <DAY> [--part <PART>] [--input <FILE>|-] | --all) [--inputs-dir <DIR>] [--runs <RUNS>] [--format <FORMAT>]
+    aoc2024 verify (--day <DAY> [--part <PART>] | --all) [--inputs-dir <DIR>] [--format <FORMAT>]
+    aoc2024 fetch (--day <DAY> | --all) [--inputs-dir <DIR>]
+    aoc2024 submit --day <DAY> --part <PART> [--input <FILE>|-] [--inputs-dir <DIR>]
+    aoc2024 new-day <DAY> [--title <TITLE>]
+    aoc2024 generate --day <DAY> [--size <SIZE>] [--seed <SEED>]
+    aoc2024 validate --day <DAY> <FILE>|-
+
+Inputs are read from <DIR>/day<DAY>/part1, where <DIR> defaults to $AOC_INPUTS or res.
+Passing - as input reads the puzzle input from stdin. Benchmarks default to 10 runs.
+With --jobs, run solves the parts on that many threads and prints a summary table.
+With --timeout, run gives up on parts taking longer than <SECS> seconds and carries on.
+With --stream, run reads the input line by line instead of loading it, which days 1, 2 and 7 support.
+<FORMAT> is table (the default), json or csv, with durations given in nanoseconds.
+Verify compares the answers with the ones recorded in <DIR>/day<DAY>/answers.
+Fetch and submit use the session token from $AOC_SESSION or ~/.config/aoc2024/session.
+Submit logs rejected answers in <DIR>/day<DAY>/guesses and never sends them again.
+New-day creates src/day<DAY> and res/day<DAY> in the current directory and registers the day.
+Generate prints a random input, scaled by <SIZE> (default 50), e.g. lines or the side of a map.
+The same <SEED> always gives the same input; without one the seed is picked at random and shown.
+Validate parses an input without solving it and points at the first line that is malformed.";
+
+const SELECTION_OPTIONS: [&str; 5] = ["--all", "--day", "--part", "--input", "--inputs-dir"];
+const DEFAULT_RUNS: usize = 10;
+const DEFAULT_SIZE: usize = 50;
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(Selection, InputSource, Option<usize>, Option<Duration>, Format),
+    Stream(Selection, InputSource),
+    Bench(Selection, InputSource, usize, Format),
+    Verify(Selection, PathBuf, Format),
+    Fetch(Selection, PathBuf),
+    Submit(u8, Part, InputSource, PathBuf),
+    NewDay(u8, Option<String>),
+    Generate(u8, usize, Option<u64>),
+    Validate(u8, InputSource),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Selection {
+    All,
+    Day(u8, Option<Part>),
+}
+
+impl Command {
+    pub fn parse(args: &[String]) -> Result<Command, String> {
+        let (command, options) = args.split_first().ok_or("Missing command")?;
+        match command.as_str() {
+            "run" => {
+                let options = Options::parse(options, &[&SELECTION_OPTIONS[..], &["--jobs", "--timeout", "--format", "--stream"]].concat())?;
+                if options.stream {
+                    if options.jobs.is_some() || options.timeout.is_some() || options.format != Format::Table {
+                        return Err("--stream cannot be combined with --jobs, --timeout or --format".to_string());
+                    }
+                    let (selection, source) = (options.selection()?, options.source()?);
+                    if source == InputSource::Stdin && !matches!(selection, Selection::Day(_, Some(_))) {
+                        return Err("--stream reads stdin only once, so it requires --part".to_string());
+                    }
+                    return Ok(Command::Stream(selection, source));
+                }
+                if options.jobs == Some(0) {
+                    return Err("--jobs must be at least 1".to_string());
+                }
+                Ok(Command::Run(options.selection()?, options.source()?, options.jobs, options.timeout, options.format))
+            }
+            "bench" => {
+                let options = Options::parse(options, &[&SELECTION_OPTIONS[..], &["--runs", "--format"]].concat())?;
+                let runs = options.runs.unwrap_or(DEFAULT_RUNS);
+                if runs == 0 {
+                    return Err("--runs must be at least 1".to_string());
+                }
+                Ok(Command::Bench(options.selection()?, options.source()?, runs, options.format))
+            }
+            "verify" => {
+                let options = Options::parse(options, &["--all", "--day", "--part", "--inputs-dir", "--format"])?;
+                let directory = options.inputs_dir.clone().unwrap_or_else(input::default_inputs_dir);
+                Ok(Command::Verify(options.selection()?, directory, options.format))
+            }
+            "fetch" => {
+                let options = Options::parse(options, &["--all", "--day", "--inputs-dir"])?;
+                let directory = options.inputs_dir.clone().unwrap_or_else(input::default_inputs_dir);
+                Ok(Command::Fetch(options.selection()?, directory))
+            }
+            "submit" => {
+                let options = Options::parse(options, &["--day", "--part", "--input", "--inputs-dir"])?;
+                let directory = options.inputs_dir.clone().unwrap_or_else(input::default_inputs_dir);
+                match options.selection()? {
+                    Selection::Day(day, Some(part)) => Ok(Command::Submit(day, part, options.source()?, directory)),
+                    _ => Err("submit requires --day and --part".to_string()),
+                }
+            }
+            "new-day" => {
+                let (day, options) = options.split_first().ok_or("Missing day")?;
+                let day = day.parse::<u8>().map_err(|_| format!("Invalid day '{}'", day))?;
+                let options = Options::parse(options, &["--title"])?;
+                Ok(Command::NewDay(day, options.title))
+            }
+            "generate" => {
+                let options = Options::parse(options, &["--day", "--size", "--seed"])?;
+                let Some(day) = options.day else { return Err("generate requires --day".to_string()) };
+                let size = options.size.unwrap_or(DEFAULT_SIZE);
+                if size == 0 {
+                    return Err("--size must be at least 1".to_string());
+                }
+                Ok(Command::Generate(day, size, options.seed))
+            }
+            "validate" => {
+                let (file, options) = options.split_last().ok_or("Missing input file")?;
+                if file.starts_with("--") {
+                    return Err("Missing input file".to_string());
+                }
+                let options = Options::parse(options, &["--day"])?;
+                let Some(day) = options.day else { return Err("validate requires --day".to_string()) };
+                Ok(Command::Validate(day, InputSource::from_argument(file)))
+            }
+            other => Err(format!("Unknown command '{}'", other)),
+        }
+    }
+
+    pub fn execute(&self) -> Result<(), String> {
+        match self {
+            Command::Run(selection, source, None, timeout, Format::Table) => {
+                let parts = selection.resolve()?;
+                let inputs = source.load_days(parts.iter().map(|(day, _)| day.number));
+                let mut unsolved = 0;
+                for (day, part) in parts {
+                    let outcome = match &inputs[&day.number] {
+                        Ok(input) => runner::run(day, part, input.clone(), *timeout),
+                        Err(error) => Outcome::Failed(error.to_string()),
+                    };
+                    let result = match &outcome {
+                        Outcome::Solved(run) => run.answer.to_string(),
+                        outcome => {
+                            unsolved += 1;
+                            let (status, message) = describe(outcome);
+                            format!("{}: {}", status, message.unwrap_or_default())
+                        }
+                    };
+                    println!("Day {} part {} ({}): {}", day.number, part.number(), day.title, result);
+                }
+                if unsolved > 0 {
+                    return Err("Not every part could be solved".to_string());
+                }
+                Ok(())
+            }
+            Command::Run(selection, source, jobs, timeout, format) => {
+                let start = Instant::now();
+                let reports = runner::run_all(&selection.resolve()?, source, jobs.unwrap_or(1), *timeout);
+                match format {
+                    Format::Table => print_reports(&reports, start.elapsed()),
+                    format => report_records(&reports).print(*format),
+                }
+                if reports.iter().any(|report| !matches!(report.outcome, Outcome::Solved(_))) {
+                    return Err("Not every part could be solved".to_string());
+                }
+                Ok(())
+            }
+            Command::Stream(selection, source) => {
+                for (day, part) in selection.resolve()? {
+                    let mut reader = source.open(day.number).map_err(|error| error.to_string())?;
+                    let answer = day
+                        .stream(reader.as_mut(), part)
+                        .ok_or(format!("Day {} cannot be streamed", day.number))?
+                        .map_err(|error| format!("Day {}: {}", day.number, error))?;
+                    println!("Day {} part {} ({}): {}", day.number, part.number(), day.title, answer);
+                }
+                Ok(())
+            }
+            Command::Bench(selection, source, runs, format) => {
+                let parts = selection.resolve()?;
+                let inputs = source.load_days(parts.iter().map(|(day, _)| day.number));
+                let benchmarks = parts
+                    .into_iter()
+                    .map(|(day, part)| BenchReport {
+                        day: day.number,
+                        part,
+                        benchmark: inputs[&day.number]
+                            .as_ref()
+                            .map_err(|error| error.to_string())
+                            .and_then(|input| bench::bench(day, part, input, *runs).map_err(|error| error.to_string())),
+                    })
+                    .collect::<Vec<_>>();
+                match format {
+                    Format::Table => print_benchmarks(&benchmarks, *runs),
+                    format => benchmark_records(&benchmarks, *runs).print(*format),
+                }
+                if benchmarks.iter().any(|report| report.benchmark.is_err()) {
+                    return Err("Not every part could be benchmarked".to_string());
+                }
+                Ok(())
+            }
+            Command::Verify(selection, directory, format) => {
+                let verifications = selection
+                    .resolve()?
+                    .into_iter()
+                    .map(|(day, part)| verify::verify(day, part, directory))
+                    .collect::<Vec<_>>();
+
+                let count = |predicate: fn(&Status) -> bool| verifications.iter().filter(|verification| predicate(&verification.status)).count();
+                let (passed, failed, missing, errors) = (
+                    count(|status| matches!(status, Status::Pass)),
+                    count(|status| matches!(status, Status::Fail { .. })),
+                    count(|status| matches!(status, Status::Missing)),
+                    count(|status| matches!(status, Status::Error(_))),
+                );
+
+                match format {
+                    Format::Table => print_verifications(&verifications, (passed, failed, missing, errors)),
+                    format => verification_records(&verifications).print(*format),
+                }
+
+                if failed + errors > 0 {
+                    return Err("Verification failed".to_string());
+                }
+                Ok(())
+            }
+            Command::Fetch(selection, directory) => {
+                let session = client::session_token().map_err(|error| error.to_string())?;
+                let client = UreqClient::default();
+                for day in selection.days()? {
+                    match fetch::fetch(&client, &session, day.number, directory).map_err(|error| error.to_string())? {
+                        Fetched::Cached(path) => println!("Day {}: already present at {}", day.number, path.display()),
+                        Fetched::Downloaded(path) => println!("Day {}: downloaded to {}", day.number, path.display()),
+                    }
+                }
+                Ok(())
+            }
+            Command::Submit(number, part, source, directory) => {
+                let day = registry::find(*number).ok_or(format!("Day {} is not implemented", number))?;
+                let input = source.load(day.number).map_err(|error| error.to_string())?;
+                let answer = day.solve(&input, *part).map_err(|error| format!("Day {}: {}", day.number, error))?;
+                let session = client::session_token().map_err(|error| error.to_string())?;
+
+                println!("Submitting day {} part {}: {}", day.number, part.number(), answer);
+                match submit::submit(&UreqClient::default(), &session, day.number, *part, &answer, directory).map_err(|error| error.to_string())? {
+                    Submission::Refused(reason) => Err(format!("Not submitted, {}", reason)),
+                    Submission::Sent(Verdict::Correct) => {
+                        println!("{}", Verdict::Correct);
+                        Ok(())
+                    }
+                    Submission::Sent(verdict) => Err(verdict.to_string()),
+                }
+            }
+            Command::Generate(number, size, seed) => {
+                let day = registry::find(*number).ok_or(format!("Day {} is not implemented", number))?;
+                let seed = seed.unwrap_or_else(|| {
+                    let seed = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_nanos() as u64;
+                    eprintln!("Generating day {} with seed {}", day.number, seed);
+                    seed
+                });
+                let input = day.generate(&mut Rng::new(seed), *size);
+                if input.is_empty() {
+                    return Err(format!("Day {} has no input generator yet", day.number));
+                }
+                print!("{}", input);
+                Ok(())
+            }
+            Command::Validate(number, source) => {
+                let day = registry::find(*number).ok_or(format!("Day {} is not implemented", number))?;
+                let input = source.load(day.number).map_err(|error| error.to_string())?;
+                match day.validate(&input) {
+                    Ok(()) => {
+                        println!("Day {}: valid input of {} lines", day.number, input.lines().count());
+                        Ok(())
+                    }
+                    Err(error) => match error.excerpt(&input) {
+                        Some(excerpt) => Err(format!("Day {}: {}\n{}", day.number, error, excerpt)),
+                        None => Err(format!("Day {}: {}", day.number, error)),
+                    },
+                }
+            }
+            Command::NewDay(day, title) => {
+                let files = scaffold::scaffold(Path::new("."), *day, title.as_deref()).map_err(|error| error.to_string())?;
+                for file in files {
+                    println!("{}", file.display());
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The benchmark of one part, or why it could not be benchmarked.
+struct BenchReport {
+    day: u8,
+    part: Part,
+    benchmark: Result<Benchmark, String>,
+}
+
+#[derive(Debug, Default)]
+struct Options {
+    all: bool,
+    day: Option<u8>,
+    part: Option<Part>,
+    input: Option<InputSource>,
+    inputs_dir: Option<PathBuf>,
+    runs: Option<usize>,
+    title: Option<String>,
+    jobs: Option<usize>,
+    size: Option<usize>,
+    seed: Option<u64>,
+    timeout: Option<Duration>,
+    format: Format,
+    stream: bool,
+}
+
+impl Options {
+    /// Parses the options following a command, accepting only the ones listed in `allowed`.
+    fn parse(options: &[String], allowed: &[&str]) -> Result<Options, String> {
+        let mut parsed = Options::default();
+
+        let mut options = options.iter();
+        while let Some(option) = options.next() {
+            if !allowed.contains(&option.as_str()) {
+                return Err(format!("Unknown option '{}'", option));
+            }
+            match option.as_str() {
+                "--all" => parsed.all = true,
+                "--stream" => parsed.stream = true,
+                "--day" => parsed.day = Some(parse_value::<u8>(option, options.next())?),
+                "--part" => parsed.part = Some(match parse_value::<u8>(option, options.next())? {
+                    1 => Part::One,
+                    2 => Part::Two,
+                    other => return Err(format!("Invalid part {}, expected 1 or 2", other)),
+                }),
+                "--input" => parsed.input = Some(InputSource::from_argument(&parse_value::<String>(option, options.next())?)),
+                "--inputs-dir" => parsed.inputs_dir = Some(parse_value::<PathBuf>(option, options.next())?),
+                "--runs" => parsed.runs = Some(parse_value::<usize>(option, options.next())?),
+                "--jobs" => parsed.jobs = Some(parse_value::<usize>(option, options.next())?),
+                "--timeout" => {
+                    let seconds = parse_value::<f64>(option, options.next())?;
+                    parsed.timeout = Some(Duration::try_from_secs_f64(seconds).ok().filter(|timeout| !timeout.is_zero()).ok_or("--timeout must be a positive number of seconds")?);
+                }
+                "--format" => parsed.format = parse_value::<String>(option, options.next())?.parse()?,
+                "--size" => parsed.size = Some(parse_value::<usize>(option, options.next())?),
+                "--seed" => parsed.seed = Some(parse_value::<u64>(option, options.next())?),
+                "--title" => parsed.title = Some(parse_value::<String>(option, options.next())?),
+                other => unreachable!("option {} is allowed but not handled", other),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    fn selection(&self) -> Result<Selection, String> {
+        match (self.all, self.day, self.part) {
+            (true, None, None) => Ok(Selection::All),
+            (true, _, _) => Err("--all cannot be combined with --day or --part".to_string()),
+            (false, Some(day), part) => Ok(Selection::Day(day, part)),
+            (false, None, Some(_)) => Err("--part requires --day".to_string()),
+            (false, None, None) => Err("Expected either --day or --all".to_string()),
+        }
+    }
+
+    fn source(&self) -> Result<InputSource, String> {
+        match (&self.input, &self.inputs_dir) {
+            (Some(_), _) if self.all => Err("--input requires --day".to_string()),
+            (Some(_), Some(_)) => Err("--input cannot be combined with --inputs-dir".to_string()),
+            (Some(input), None) => Ok(input.clone()),
+            (None, Some(directory)) => Ok(InputSource::Directory(directory.clone())),
+            (None, None) => Ok(InputSource::default()),
+        }
+    }
+}
+
+impl Selection {
+    fn days(&self) -> Result<Vec<&'static registry::Day>, String> {
+        let mut days = self.resolve()?.into_iter().map(|(day, _)| day).collect::<Vec<_>>();
+        days.dedup_by_key(|day| day.number);
+        Ok(days)
+    }
+
+    fn resolve(&self) -> Result<Vec<(&'static registry::Day, Part)>, String> {
+        match self {
+            Selection::All => Ok(registry::DAYS
+                .iter()
+                .flat_map(|day| Part::ALL.into_iter().map(move |part| (day, part)))
+                .collect()),
+            Selection::Day(number, part) => {
+                let day = registry::find(*number).ok_or(format!("Day {} is not implemented", number))?;
+                match part {
+                    Some(part) => Ok(vec![(day, *part)]),
+                    None => Ok(Part::ALL.into_iter().map(|part| (day, part)).collect()),
+                }
+            }
+        }
+    }
+}
+
+fn print_benchmarks(reports: &[BenchReport], runs: usize) {
+    let statistics = |statistics: &Statistics| format!(
+        "{:>10} {:>10} {:>10}",
+        bench::format_duration(statistics.min),
+        bench::format_duration(statistics.median),
+        bench::format_duration(statistics.max)
+    );
+
+    println!("Benchmarking {} runs per part, parse and solve as min / median / max", runs);
+    println!("{:>3} {:>4}  {:>32}  {:>32}  Status", "Day", "Part", "Parse", "Solve");
+    for report in reports {
+        let (parse, solve, status) = match &report.benchmark {
+            Ok(benchmark) => (
+                benchmark.parse.as_ref().map(statistics).unwrap_or_else(|| "included in solve".to_string()),
+                statistics(&benchmark.solve),
+                "ok".to_string(),
+            ),
+            Err(message) => (String::new(), String::new(), format!("failed: {}", message)),
+        };
+        println!("{:>3} {:>4}  {:>32}  {:>32}  {}", report.day, report.part.number(), parse, solve, status);
+    }
+
+    let benchmarks = reports.iter().filter_map(|report| report.benchmark.as_ref().ok()).collect::<Vec<_>>();
+    let parse_total = benchmarks.iter().filter_map(|benchmark| benchmark.parse.map(|parse| parse.median)).sum();
+    let solve_total = benchmarks.iter().map(|benchmark| benchmark.solve.median).sum();
+    println!(
+        "Total of medians: parse {}, solve {}, overall {}",
+        bench::format_duration(parse_total),
+        bench::format_duration(solve_total),
+        bench::format_duration(parse_total + solve_total)
+    );
+}
+
+fn print_verifications(verifications: &[Verification], (passed, failed, missing, errors): (usize, usize, usize, usize)) {
+    for verification in verifications {
+        let answer = verification.answer.as_ref().map(|answer| answer.to_string()).unwrap_or_default();
+        let status = match &verification.status {
+            Status::Pass => format!("pass     {}", answer),
+            Status::Fail { expected } => format!("FAIL     {} (expected {})", answer, expected),
+            Status::Missing => format!("missing  {}", answer),
+            Status::Error(message) => format!("ERROR    {}", message),
+        };
+        println!("Day {:>2} part {}: {}", verification.day, verification.part.number(), status);
+    }
+    println!("{} passed, {} failed, {} missing, {} errors", passed, failed, missing, errors);
+}
+
+fn verification_records(verifications: &[Verification]) -> Records {
+    let mut records = Records::new(&["day", "part", "answer", "expected", "time_ns", "status", "message"]);
+    for verification in verifications {
+        let (expected, status, message) = match &verification.status {
+            Status::Pass => (verification.answer.as_ref().map(|answer| answer.to_string()), "pass", None),
+            Status::Fail { expected } => (Some(expected.to_string()), "fail", None),
+            Status::Missing => (None, "missing", None),
+            Status::Error(message) => (None, "error", Some(message.clone())),
+        };
+        records.push(vec![
+            verification.day.into(),
+            verification.part.number().into(),
+            verification.answer.as_ref().map(|answer| answer.to_string()).into(),
+            expected.into(),
+            verification.time.into(),
+            status.into(),
+            message.into(),
+        ]);
+    }
+    records
+}
+
+fn benchmark_records(reports: &[BenchReport], runs: usize) -> Records {
+    let mut records = Records::new(&[
+        "day",
+        "part",
+        "answer",
+        "runs",
+        "parse_min_ns",
+        "parse_median_ns",
+        "parse_max_ns",
+        "solve_min_ns",
+        "solve_median_ns",
+        "solve_max_ns",
+        "status",
+        "message",
+    ]);
+    for report in reports {
+        let benchmark = report.benchmark.as_ref().ok();
+        let parse = benchmark.and_then(|benchmark| benchmark.parse);
+        let solve = benchmark.map(|benchmark| benchmark.solve);
+        let (status, message) = match &report.benchmark {
+            Ok(_) => ("ok", None),
+            Err(message) => ("failed", Some(message.clone())),
+        };
+        records.push(vec![
+            report.day.into(),
+            report.part.number().into(),
+            benchmark.map(|benchmark| benchmark.answer.to_string()).into(),
+            runs.into(),
+            parse.map(|parse| parse.min).into(),
+            parse.map(|parse| parse.median).into(),
+            parse.map(|parse| parse.max).into(),
+            solve.map(|solve| solve.min).into(),
+            solve.map(|solve| solve.median).into(),
+            solve.map(|solve| solve.max).into(),
+            status.into(),
+            message.into(),
+        ]);
+    }
+    records
+}
+
+fn report_records(reports: &[Report]) -> Records {
+    let mut records = Records::new(&["day", "part", "title", "answer", "parse_ns", "solve_ns", "status", "message"]);
+    for report in reports {
+        let (answer, parse, solve) = match &report.outcome {
+            Outcome::Solved(run) => (Some(run.answer.to_string()), run.parse_time, Some(run.solve_time)),
+            _ => (None, None, None),
+        };
+        let (status, message) = describe(&report.outcome);
+        records.push(vec![
+            report.day.number.into(),
+            report.part.number().into(),
+            report.day.title.into(),
+            answer.into(),
+            parse.into(),
+            solve.into(),
+            status.into(),
+            message.into(),
+        ]);
+    }
+    records
+}
+
+fn print_reports(reports: &[Report], wall_time: Duration) {
+    println!("{:>3} {:>4}  {:<22} {:>16} {:>10} {:>10}  Status", "Day", "Part", "Title", "Answer", "Parse", "Solve");
+    for report in reports {
+        let (answer, parse, solve) = match &report.outcome {
+            Outcome::Solved(run) => (run.answer.to_string(), run.parse_time.map(bench::format_duration).unwrap_or_else(|| "-".to_string()), bench::format_duration(run.solve_time)),
+            _ => (String::new(), String::new(), String::new()),
+        };
+        let status = match describe(&report.outcome) {
+            (status, Some(message)) => format!("{}: {}", status, message),
+            (status, None) => status.to_string(),
+        };
+        println!("{:>3} {:>4}  {:<22} {:>16} {:>10} {:>10}  {}", report.day.number, report.part.number(), report.day.title, answer, parse, solve, status);
+    }
+
+    let count = |predicate: fn(&Outcome) -> bool| reports.iter().filter(|report| predicate(&report.outcome)).count();
+    let solve_total = reports
+        .iter()
+        .filter_map(|report| match &report.outcome {
+            Outcome::Solved(run) => Some(run.total_time()),
+            _ => None,
+        })
+        .sum();
+    println!(
+        "{} solved, {} failed, {} panicked, {} timed out in {} ({} spent parsing and solving)",
+        count(|outcome| matches!(outcome, Outcome::Solved(_))),
+        count(|outcome| matches!(outcome, Outcome::Failed(_))),
+        count(|outcome| matches!(outcome, Outcome::Panicked(_))),
+        count(|outcome| matches!(outcome, Outcome::TimedOut(_))),
+        bench::format_duration(wall_time),
+        bench::format_duration(solve_total)
+    );
+}
+
+/// The status of an outcome and what went wrong, if anything.
+fn describe(outcome: &Outcome) -> (&'static str, Option<String>) {
+    match outcome {
+        Outcome::Solved(_) => ("ok", None),
+        Outcome::Failed(message) => ("failed", Some(message.clone())),
+        Outcome::Panicked(message) => ("panicked", Some(message.clone())),
+        Outcome::TimedOut(timeout) => ("timed out", Some(format!("still running after {}", bench::format_duration(*timeout)))),
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(option: &str, value: Option<&String>) -> Result<T, String> {
+    value
+        .ok_or(format!("Missing value for {}", option))?
+        .parse::<T>()
+        .map_err(|_| format!("Invalid value for {}", option))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(input: &str) -> Vec<String> {
+        input.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parse_day_and_part() {
+        assert_eq!(Ok(Command::Run(Selection::Day(6, Some(Part::Two)), InputSource::default(), None, None, Format::Table)), Command::parse(&args("run --day 6 --part 2")));
+        assert_eq!(Ok(Command::Run(Selection::Day(6, None), InputSource::default(), None, None, Format::Table)), Command::parse(&args("run --day 6")));
+        assert_eq!(Ok(Command::Run(Selection::All, InputSource::default(), None, None, Format::Table)), Command::parse(&args("run --all")));
+        assert_eq!(Ok(Command::Run(Selection::All, InputSource::default(), Some(4), None, Format::Table)), Command::parse(&args("run --all --jobs 4")));
+        assert_eq!(Ok(Command::Run(Selection::Day(6, None), InputSource::default(), None, None, Format::Json)), Command::parse(&args("run --day 6 --format json")));
+        assert_eq!(
+            Ok(Command::Run(Selection::All, InputSource::default(), Some(2), Some(Duration::from_millis(1500)), Format::Table)),
+            Command::parse(&args("run --all --jobs 2 --timeout 1.5"))
+        );
+    }
+
+    #[test]
+    fn parse_input_source() {
+        assert_eq!(
+            Ok(Command::Run(Selection::Day(1, None), InputSource::File(PathBuf::from("other/input")), None, None, Format::Table)),
+            Command::parse(&args("run --day 1 --input other/input"))
+        );
+        assert_eq!(Ok(Command::Run(Selection::Day(1, None), InputSource::Stdin, None, None, Format::Table)), Command::parse(&args("run --day 1 --input -")));
+        assert_eq!(
+            Ok(Command::Run(Selection::All, InputSource::Directory(PathBuf::from("inputs")), None, None, Format::Table)),
+            Command::parse(&args("run --all --inputs-dir inputs"))
+        );
+        assert!(Command::parse(&args("run --all --input other/input")).is_err());
+        assert!(Command::parse(&args("run --day 1 --input other/input --inputs-dir inputs")).is_err());
+    }
+
+    #[test]
+    fn parse_stream() {
+        assert_eq!(Ok(Command::Stream(Selection::Day(7, None), InputSource::default())), Command::parse(&args("run --day 7 --stream")));
+        assert_eq!(
+            Ok(Command::Stream(Selection::Day(1, Some(Part::Two)), InputSource::Stdin)),
+            Command::parse(&args("run --day 1 --part 2 --input - --stream"))
+        );
+        assert!(Command::parse(&args("run --day 1 --input - --stream")).is_err());
+        assert!(Command::parse(&args("run --all --jobs 2 --stream")).is_err());
+        assert!(Command::parse(&args("run --day 1 --stream --format json")).is_err());
+    }
+
+    #[test]
+    fn reject_invalid_arguments() {
+        assert!(Command::parse(&args("")).is_err());
+        assert!(Command::parse(&args("run")).is_err());
+        assert!(Command::parse(&args("run --part 1")).is_err());
+        assert!(Command::parse(&args("run --day 6 --part 3")).is_err());
+        assert!(Command::parse(&args("run --all --day 6")).is_err());
+        assert!(Command::parse(&args("jump --day 6")).is_err());
+        assert!(Command::parse(&args("run --day 6 --runs 3")).is_err());
+        assert!(Command::parse(&args("bench --day 6 --runs 0")).is_err());
+        assert!(Command::parse(&args("run --all --jobs 0")).is_err());
+        assert!(Command::parse(&args("bench --all --jobs 2")).is_err());
+        assert!(Command::parse(&args("run --all --format xml")).is_err());
+        assert!(Command::parse(&args("run --all --timeout 0")).is_err());
+        assert!(Command::parse(&args("run --all --timeout soon")).is_err());
+        assert!(Command::parse(&args("fetch --all --format json")).is_err());
+    }
+
+    #[test]
+    fn parse_verify() {
+        assert_eq!(Ok(Command::Verify(Selection::All, PathBuf::from("inputs"), Format::Table)), Command::parse(&args("verify --all --inputs-dir inputs")));
+        assert_eq!(Ok(Command::Verify(Selection::Day(9, None), input::default_inputs_dir(), Format::Table)), Command::parse(&args("verify --day 9")));
+        assert_eq!(Ok(Command::Verify(Selection::All, input::default_inputs_dir(), Format::Json)), Command::parse(&args("verify --all --format json")));
+        assert!(Command::parse(&args("verify --day 9 --input other/input")).is_err());
+    }
+
+    #[test]
+    fn parse_bench() {
+        assert_eq!(Ok(Command::Bench(Selection::All, InputSource::default(), 3, Format::Table)), Command::parse(&args("bench --all --runs 3")));
+        assert_eq!(
+            Ok(Command::Bench(Selection::Day(9, Some(Part::Two)), InputSource::default(), DEFAULT_RUNS, Format::Csv)),
+            Command::parse(&args("bench --day 9 --part 2 --format csv"))
+        );
+    }
+
+    #[test]
+    fn parse_fetch() {
+        assert_eq!(Ok(Command::Fetch(Selection::Day(4, None), PathBuf::from("inputs"))), Command::parse(&args("fetch --day 4 --inputs-dir inputs")));
+        assert_eq!(Ok(Command::Fetch(Selection::All, input::default_inputs_dir())), Command::parse(&args("fetch --all")));
+        assert!(Command::parse(&args("fetch --day 4 --part 1")).is_err());
+    }
+
+    #[test]
+    fn parse_submit() {
+        assert_eq!(
+            Ok(Command::Submit(5, Part::Two, InputSource::default(), input::default_inputs_dir())),
+            Command::parse(&args("submit --day 5 --part 2"))
+        );
+        assert!(Command::parse(&args("submit --day 5")).is_err());
+        assert!(Command::parse(&args("submit --all")).is_err());
+    }
+
+    #[test]
+    fn parse_new_day() {
+        assert_eq!(Ok(Command::NewDay(14, None)), Command::parse(&args("new-day 14")));
+        assert_eq!(
+            Ok(Command::NewDay(14, Some("Restroom Redoubt".to_string()))),
+            Command::parse(&["new-day", "14", "--title", "Restroom Redoubt"].map(String::from))
+        );
+        assert!(Command::parse(&args("new-day")).is_err());
+        assert!(Command::parse(&args("new-day fourteen")).is_err());
+    }
+
+    #[test]
+    fn parse_generate() {
+        assert_eq!(Ok(Command::Generate(6, 130, Some(7))), Command::parse(&args("generate --day 6 --size 130 --seed 7")));
+        assert_eq!(Ok(Command::Generate(6, DEFAULT_SIZE, None)), Command::parse(&args("generate --day 6")));
+        assert!(Command::parse(&args("generate --all")).is_err());
+        assert!(Command::parse(&args("generate --day 6 --size 0")).is_err());
+        assert!(Command::parse(&args("generate --day 6 --seed -1")).is_err());
+    }
+
+    #[test]
+    fn parse_validate() {
+        assert_eq!(Ok(Command::Validate(5, InputSource::File(PathBuf::from("input.txt")))), Command::parse(&args("validate --day 5 input.txt")));
+        assert_eq!(Ok(Command::Validate(5, InputSource::Stdin)), Command::parse(&args("validate --day 5 -")));
+        assert!(Command::parse(&args("validate --day 5")).is_err());
+        assert!(Command::parse(&args("validate input.txt")).is_err());
+        assert!(Command::parse(&args("validate --day 5 --part 1 input.txt")).is_err());
+    }
+
+    #[test]
+    fn resolve_all_parts_of_day() {
+        let selected = Selection::Day(3, None).resolve().unwrap();
+        assert_eq!(vec![(3, Part::One), (3, Part::Two)], selected.iter().map(|(day, part)| (day.number, *part)).collect::<Vec<_>>());
+        assert!(Selection::Day(26, None).resolve().is_err());
+        assert_eq!(registry::DAYS.len(), Selection::All.days().unwrap().len());
+    }
+}
@@ -0,0 +1,171 @@
+use crate::error::AocError;
+use std::path::PathBuf;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const SESSION_VARIABLE: &str = "AOC_SESSION";
+
+const USER_AGENT: &str = concat!("aoc2024/", env!("CARGO_PKG_VERSION"), " (command line solver)");
+
+/// A response from the puzzle server, including error responses.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// The requests made to the puzzle server, authenticated with the `session` cookie of a logged in
+/// user. Paths are relative to the server, e.g. `/2024/day/1/input`.
+pub trait HttpClient {
+    fn get(&self, path: &str, session: &str) -> Result<Response, AocError>;
+
+    /// Posts `form` URL-encoded.
+    fn post(&self, path: &str, session: &str, form: &[(&str, &str)]) -> Result<Response, AocError>;
+}
+
+/// Talks to the puzzle server, or any server reachable at `base_url`, over HTTP.
+pub struct UreqClient {
+    agent: ureq::Agent,
+    base_url: String,
+}
+
+impl UreqClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        UreqClient {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+            base_url: base_url.into(),
+        }
+    }
+
+    fn response(&self, result: Result<ureq::Response, ureq::Error>, path: &str) -> Result<Response, AocError> {
+        let response = match result {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(error) => return Err(AocError::Http(format!("Request failed: {}", error))),
+        };
+
+        let status = response.status();
+        let body = response
+            .into_string()
+            .map_err(|error| AocError::Http(format!("Could not read response from {}{}: {}", self.base_url, path, error)))?;
+        Ok(Response { status, body })
+    }
+}
+
+impl Default for UreqClient {
+    fn default() -> Self {
+        UreqClient::new(DEFAULT_BASE_URL)
+    }
+}
+
+impl HttpClient for UreqClient {
+    fn get(&self, path: &str, session: &str) -> Result<Response, AocError> {
+        let request = self.agent
+            .get(&format!("{}{}", self.base_url, path))
+            .set("Cookie", &format!("session={}", session));
+        self.response(request.call(), path)
+    }
+
+    fn post(&self, path: &str, session: &str, form: &[(&str, &str)]) -> Result<Response, AocError> {
+        let request = self.agent
+            .post(&format!("{}{}", self.base_url, path))
+            .set("Cookie", &format!("session={}", session));
+        self.response(request.send_form(form), path)
+    }
+}
+
+/// The session token from `AOC_SESSION`, or else from the `session` file in the configuration
+/// directory.
+pub fn session_token() -> Result<String, AocError> {
+    if let Some(token) = std::env::var(SESSION_VARIABLE).ok().filter(|token| !token.trim().is_empty()) {
+        return Ok(token.trim().to_string());
+    }
+
+    let path = session_path()
+        .ok_or_else(|| AocError::Input(format!("No session token, set {} or create a session file", SESSION_VARIABLE)))?;
+    match std::fs::read_to_string(&path) {
+        Ok(token) if !token.trim().is_empty() => Ok(token.trim().to_string()),
+        Ok(_) => Err(AocError::Input(format!("Session file {} is empty", path.display()))),
+        Err(_) => Err(AocError::Input(format!("No session token, set {} or write it to {}", SESSION_VARIABLE, path.display()))),
+    }
+}
+
+/// `$XDG_CONFIG_HOME/aoc2024/session`, defaulting to `~/.config/aoc2024/session`.
+pub fn session_path() -> Option<PathBuf> {
+    let config = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config.join("aoc2024").join("session"))
+}
+
+/// A server answering every connection with a canned response, for testing clients offline.
+#[cfg(test)]
+pub(crate) mod mock {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+
+    /// Serves `responses` to consecutive connections on a free local port and returns the base URL
+    /// together with a handle yielding the raw requests it received.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = std::thread::spawn(move || {
+            responses.into_iter().map(|(status, body)| {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                while reader.read_line(&mut request).unwrap() > 2 {}
+
+                let length = request
+                    .lines()
+                    .find_map(|line| line.to_ascii_lowercase().strip_prefix("content-length:").map(|length| length.trim().parse::<usize>().unwrap()))
+                    .unwrap_or(0);
+                let mut content = vec![0; length];
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8(content).unwrap());
+
+                let response = format!("HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body);
+                reader.into_inner().write_all(response.as_bytes()).unwrap();
+                request
+            }).collect()
+        });
+
+        (url, handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn send_session_cookie() {
+        let (url, server) = mock::serve(vec![(200, "1 2\n3 4\n"), (404, "Not Found")]);
+        let client = UreqClient::new(url);
+
+        assert_eq!(Ok(Response { status: 200, body: "1 2\n3 4\n".to_string() }), client.get("/2024/day/1/input", "secret"));
+        assert_eq!(Ok(Response { status: 404, body: "Not Found".to_string() }), client.get("/2024/day/26/input", "secret"));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2024/day/1/input HTTP/1.1"));
+        assert!(requests[0].contains("session=secret"));
+    }
+
+    #[test]
+    fn post_form() {
+        let (url, server) = mock::serve(vec![(200, "<article>That's the right answer!</article>")]);
+        let response = UreqClient::new(url).post("/2024/day/1/answer", "secret", &[("level", "1"), ("answer", "11")]).unwrap();
+        assert_eq!(200, response.status);
+
+        let request = &server.join().unwrap()[0];
+        assert!(request.starts_with("POST /2024/day/1/answer HTTP/1.1"));
+        assert!(request.ends_with("level=1&answer=11"));
+    }
+
+    #[test]
+    fn report_unreachable_server() {
+        let error = UreqClient::new("http://127.0.0.1:1").get("/2024/day/1/input", "secret").unwrap_err();
+        assert!(matches!(error, AocError::Http(_)));
+    }
+}
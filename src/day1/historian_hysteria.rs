@@ -1,34 +1,352 @@
-use std::collections::HashMap;
-
-pub fn pair_distances(input: &str) -> u32 {
-    let (mut left, mut right) = input
-        .lines()
-        .map(|line| line.split_whitespace().collect::<Vec<_>>())
-        .map(|parts| (parts[0].parse::<u32>().unwrap(), parts[1].parse::<u32>().unwrap()))
-        .fold((Vec::new(), Vec::new()), |(mut left_vec, mut right_vec), (lhs, rhs)| {
-            left_vec.push(lhs);
-            right_vec.push(rhs);
-            
-            (left_vec, right_vec)
+use crate::error::AocError;
+use crate::parsing;
+use std::collections::BTreeMap;
+use std::io::BufRead;
+
+/// Calls `row` with the location IDs on every line of `reader`. Every line needs `columns` IDs,
+/// or as many as the first line if `columns` is `None`. All the ways of reading location lists
+/// share this parser.
+fn read_locations(reader: impl BufRead, mut columns: Option<usize>, mut row: impl FnMut(&[u32])) -> Result<(), AocError> {
+    let mut ids = Vec::new();
+    parsing::read_lines(reader, |line| {
+        ids.clear();
+        for id in line.split_whitespace() {
+            ids.push(parsing::number::<u32>(line, id)?);
+        }
+        match columns {
+            None if ids.is_empty() => return Err(AocError::at(line, line, "expected at least one location ID")),
+            None => columns = Some(ids.len()),
+            Some(columns) if ids.len() != columns => {
+                return Err(AocError::at(line, line, format!("expected {} location IDs, found {}", columns, ids.len())))
+            }
+            Some(_) => (),
+        }
+        row(&ids);
+        Ok(())
+    })
+}
+
+/// Parses any number of whitespace separated lists, which need the same number of columns on every
+/// line. The lists are returned in column order.
+pub fn location_columns(input: &str) -> Result<Vec<Vec<u32>>, AocError> {
+    let mut columns: Vec<Vec<u32>> = Vec::new();
+    read_locations(input.as_bytes(), None, |ids| {
+        if columns.is_empty() {
+            columns.resize(ids.len(), Vec::new());
+        }
+        columns.iter_mut().zip(ids).for_each(|(column, id)| column.push(*id));
+    })?;
+    Ok(columns)
+}
+
+/// How often each location ID appears in a list, ordered by ID.
+type Counts = BTreeMap<u32, u64>;
+
+/// Counts the IDs of the left and right list, which takes memory for the distinct IDs only rather
+/// than for every line.
+fn count_locations(reader: impl BufRead) -> Result<(Counts, Counts), AocError> {
+    let (mut left, mut right) = (BTreeMap::new(), BTreeMap::new());
+    read_locations(reader, Some(2), |ids| {
+        *left.entry(ids[0]).or_insert(0) += 1;
+        *right.entry(ids[1]).or_insert(0) += 1;
+    })?;
+    Ok((left, right))
+}
+
+/// [`pair_distances`] for lists read line by line from `reader`, holding only the counts of the
+/// distinct IDs.
+pub fn pair_distances_from(reader: impl BufRead) -> Result<u64, AocError> {
+    let (left, right) = count_locations(reader)?;
+    let (mut left, mut right) = (left.into_iter(), right.into_iter());
+    let (mut lhs, mut rhs) = (left.next(), right.next());
+
+    let mut distances = 0;
+    while let (Some((left_id, left_count)), Some((right_id, right_count))) = (lhs.as_mut(), rhs.as_mut()) {
+        let pairs = (*left_count).min(*right_count);
+        distances += pairs * left_id.abs_diff(*right_id) as u64;
+        *left_count -= pairs;
+        *right_count -= pairs;
+        if *left_count == 0 {
+            lhs = left.next();
+        }
+        if *right_count == 0 {
+            rhs = right.next();
+        }
+    }
+    Ok(distances)
+}
+
+/// [`similarity_score`] for lists read line by line from `reader`, holding only the counts of the
+/// distinct IDs.
+pub fn similarity_score_from(reader: impl BufRead) -> Result<u64, AocError> {
+    let (left, right) = count_locations(reader)?;
+    Ok(left.iter().map(|(id, count)| *id as u64 * count * right.get(id).unwrap_or(&0)).sum())
+}
+
+/// Sums the distances between the smallest, second smallest, ... IDs of both lists.
+pub fn pair_distances(input: &str) -> Result<u64, AocError> {
+    pair_distances_from(input.as_bytes())
+}
+
+/// Sums every left ID multiplied by the number of times it appears in the right list.
+pub fn similarity_score(input: &str) -> Result<u64, AocError> {
+    similarity_score_from(input.as_bytes())
+}
+
+/// Both location lists, sorted once so that they can be compared in several ways, together with
+/// how often each ID appears in either list.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LocationLists {
+    left: Vec<u32>,
+    right: Vec<u32>,
+    frequencies: Vec<Frequency>,
+}
+
+/// How often an ID appears in each list.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Frequency {
+    pub id: u32,
+    pub left: usize,
+    pub right: usize,
+}
+
+impl LocationLists {
+    /// Sorts both lists and counts their IDs from the sorted runs.
+    pub fn new(mut left: Vec<u32>, mut right: Vec<u32>) -> Self {
+        left.sort_unstable();
+        right.sort_unstable();
+        let frequencies = merge_frequencies(&left, &right).collect();
+        LocationLists { left, right, frequencies }
+    }
+
+    /// Reads both lists in a single pass over the input.
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let (mut left, mut right) = (Vec::new(), Vec::new());
+        read_locations(input.as_bytes(), Some(2), |ids| {
+            left.push(ids[0]);
+            right.push(ids[1]);
+        })?;
+        Ok(LocationLists::new(left, right))
+    }
+
+    pub fn left(&self) -> &[u32] {
+        &self.left
+    }
+
+    pub fn right(&self) -> &[u32] {
+        &self.right
+    }
+
+    /// The total distance between the smallest, second smallest, ... IDs of both lists, like
+    /// [`pair_distances`]. No other way of pairing the IDs has a smaller total distance, so this
+    /// is also the optimal assignment for absolute differences.
+    pub fn distance(&self) -> u64 {
+        sorted_distance(&self.left, &self.right)
+    }
+
+    /// Every left ID multiplied by the number of times it appears in the right list, like
+    /// [`similarity_score`].
+    pub fn similarity(&self) -> u64 {
+        similarity(self.frequencies.iter().copied())
+    }
+
+    /// The number of IDs both lists have in common, counting repeated IDs as often as they appear
+    /// in both lists.
+    pub fn overlap(&self) -> usize {
+        self.frequencies.iter().map(|frequency| frequency.left.min(frequency.right)).sum()
+    }
+
+    /// The share of distinct IDs appearing in both lists, between 0 and 1.
+    pub fn jaccard_index(&self) -> f64 {
+        let shared = self.frequencies.iter().filter(|frequency| frequency.left > 0 && frequency.right > 0).count();
+        ratio(shared, self.frequencies.len())
+    }
+
+    /// Like [`jaccard_index`](Self::jaccard_index), but weighting every ID by how often it appears.
+    pub fn weighted_jaccard_index(&self) -> f64 {
+        let union = self.frequencies.iter().map(|frequency| frequency.left.max(frequency.right)).sum();
+        ratio(self.overlap(), union)
+    }
+
+    /// How often every ID appears in each list, ordered by ID.
+    pub fn frequencies(&self) -> &[Frequency] {
+        &self.frequencies
+    }
+
+    /// The `k` pairs of the sorted lists furthest apart, the furthest first. Pairs equally far
+    /// apart keep their order.
+    pub fn most_divergent(&self, k: usize) -> Vec<(u32, u32)> {
+        let mut pairs = self.left.iter().copied().zip(self.right.iter().copied()).collect::<Vec<_>>();
+        pairs.sort_by_key(|(left, right)| std::cmp::Reverse(left.abs_diff(*right)));
+        pairs.truncate(k);
+        pairs
+    }
+}
+
+/// The distance and similarity between every pair of lists, indexed by their columns. Both
+/// matrices are symmetric.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComparisonMatrix {
+    pub distances: Vec<Vec<u64>>,
+    pub similarities: Vec<Vec<u64>>,
+}
+
+impl ComparisonMatrix {
+    /// Sorts every column once, however many other columns it is compared with.
+    pub fn new(columns: &[Vec<u32>]) -> Self {
+        let count = columns.len();
+        let sorted = columns
+            .iter()
+            .map(|column| {
+                let mut column = column.clone();
+                column.sort_unstable();
+                column
+            })
+            .collect::<Vec<_>>();
+
+        let mut matrix = ComparisonMatrix { distances: vec![vec![0; count]; count], similarities: vec![vec![0; count]; count] };
+        for i in 0..count {
+            for j in i..count {
+                let (distance, similarity) = (sorted_distance(&sorted[i], &sorted[j]), similarity(merge_frequencies(&sorted[i], &sorted[j])));
+                matrix.distances[i][j] = distance;
+                matrix.distances[j][i] = distance;
+                matrix.similarities[i][j] = similarity;
+                matrix.similarities[j][i] = similarity;
+            }
+        }
+        matrix
+    }
+
+    /// The two different lists agreeing most, which are the closest ones with the highest
+    /// similarity breaking ties, or `None` for fewer than two lists.
+    pub fn closest_lists(&self) -> Option<(usize, usize)> {
+        let count = self.distances.len();
+        (0..count)
+            .flat_map(|i| (i + 1..count).map(move |j| (i, j)))
+            .min_by_key(|&(i, j)| (self.distances[i][j], std::cmp::Reverse(self.similarities[i][j])))
+    }
+}
+
+/// The distance between the pairs of two sorted lists.
+fn sorted_distance(left: &[u32], right: &[u32]) -> u64 {
+    left.iter().zip(right).map(|(left, right)| left.abs_diff(*right) as u64).sum()
+}
+
+/// Every left ID multiplied by the number of times it appears in the right list.
+fn similarity(frequencies: impl Iterator<Item = Frequency>) -> u64 {
+    frequencies.map(|frequency| frequency.id as u64 * frequency.left as u64 * frequency.right as u64).sum()
+}
+
+/// How often every ID appears in two sorted lists, ordered by ID, walking the runs of equal IDs of
+/// both at once.
+fn merge_frequencies<'a>(left: &'a [u32], right: &'a [u32]) -> impl Iterator<Item = Frequency> + 'a {
+    let (mut left_runs, mut right_runs) = (left.chunk_by(u32::eq).peekable(), right.chunk_by(u32::eq).peekable());
+    std::iter::from_fn(move || {
+        let id = match (left_runs.peek(), right_runs.peek()) {
+            (Some(left), Some(right)) => left[0].min(right[0]),
+            (Some(run), None) | (None, Some(run)) => run[0],
+            (None, None) => return None,
+        };
+        let left = left_runs.next_if(|run| run[0] == id).map_or(0, <[u32]>::len);
+        let right = right_runs.next_if(|run| run[0] == id).map_or(0, <[u32]>::len);
+        Some(Frequency { id, left, right })
+    })
+}
+
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        return 0.0;
+    }
+    numerator as f64 / denominator as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day1::HistorianHysteria;
+    use crate::random::{self, Rng};
+    use crate::solution::Solution;
+
+    #[test]
+    fn report_malformed_lines() {
+        assert_eq!(Ok(LocationLists::new(vec![3, 4], vec![4, 3])), LocationLists::parse("3   4\n4   3\n"));
+        assert_eq!(Err(AocError::parse(2, 1, "expected 2 location IDs, found 1")), LocationLists::parse("3   4\n4\n"));
+        assert_eq!(Err(AocError::parse(1, 1, "expected 2 location IDs, found 3")), LocationLists::parse("1 2 3\n"));
+        assert_eq!(Err(AocError::parse(1, 5, "invalid number '-4'")), LocationLists::parse("3   -4\n"));
+    }
+
+    fn example() -> LocationLists {
+        LocationLists::parse("3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n").unwrap()
+    }
+
+    #[test]
+    fn compare_lists() {
+        let lists = example();
+        assert_eq!(11, lists.distance());
+        assert_eq!(31, lists.similarity());
+        assert_eq!(4, lists.overlap());
+        assert_eq!(2.0 / 6.0, lists.jaccard_index());
+        assert_eq!(0.5, lists.weighted_jaccard_index());
+        assert_eq!(Some(&Frequency { id: 3, left: 3, right: 3 }), lists.frequencies().iter().find(|frequency| frequency.id == 3));
+        assert_eq!(6, lists.frequencies().len());
+        assert_eq!(vec![(4, 9), (1, 3)], lists.most_divergent(2));
+        assert_eq!(6, lists.most_divergent(10).len());
+    }
+
+    #[test]
+    fn compare_columns() {
+        let columns = location_columns("3 4 3\n4 3 4\n2 5 2\n1 3 1\n3 9 4\n3 3 3\n").unwrap();
+        assert_eq!(3, columns.len());
+        assert_eq!(vec![3, 4, 2, 1, 4, 3], columns[2]);
+
+        let matrix = ComparisonMatrix::new(&columns);
+        assert_eq!(vec![0, 11, 1], matrix.distances[0]);
+        assert_eq!(matrix.distances[1][0], matrix.distances[0][1]);
+        assert_eq!(31, matrix.similarities[0][1]);
+        assert_eq!(example().similarity(), matrix.similarities[0][1]);
+        assert_eq!(Some((0, 2)), matrix.closest_lists());
+        assert_eq!(None, ComparisonMatrix::new(&columns[..1]).closest_lists());
+
+        assert_eq!(Err(AocError::parse(2, 1, "expected 3 location IDs, found 2")), location_columns("1 2 3\n4 5\n"));
+        assert_eq!(Err(AocError::parse(1, 1, "expected at least one location ID")), location_columns("\n1\n"));
+        assert_eq!(Ok(Vec::new()), location_columns(""));
+    }
+
+    #[test]
+    fn pair_sorted_lists_optimally() {
+        random::check(300, |rng| {
+            let len = rng.range(0..=5);
+            let list = |rng: &mut Rng| (0..len).map(|_| rng.range(0..=30) as u32).collect::<Vec<_>>();
+            let lists = LocationLists::new(list(rng), list(rng));
+            assert_eq!(assignment_naive(lists.left(), lists.right()), lists.distance(), "{:?}", lists);
         });
-    
-    left.sort();
-    right.sort();
-    
-    left.iter().zip(right.iter()).map(|(lhs, rhs)| lhs.abs_diff(*rhs)).sum()
-} 
-
-pub fn similarity_score(input: &str) -> u32 {
-    let (left, right) = input
-        .lines()
-        .map(|line| line.split_whitespace().collect::<Vec<_>>())
-        .map(|parts| (parts[0].parse::<u32>().unwrap(), parts[1].parse::<u32>().unwrap()))
-        .fold((Vec::new(), HashMap::new()), |(mut left_vec, mut right_vec), (lhs, rhs)| {
-            left_vec.push(lhs);
-            *right_vec.entry(rhs).or_insert(0u32) += 1;
-
-            (left_vec, right_vec)
+    }
+
+    /// Tries every way of pairing the IDs of two lists of the same length.
+    fn assignment_naive(left: &[u32], right: &[u32]) -> u64 {
+        let Some((first, rest)) = left.split_first() else { return 0 };
+        (0..right.len())
+            .map(|index| {
+                let mut others = right.to_vec();
+                let paired = others.remove(index);
+                first.abs_diff(paired) as u64 + assignment_naive(rest, &others)
+            })
+            .min()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn stream_lists() {
+        let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+        assert_eq!(Ok(11), pair_distances(input));
+        assert_eq!(Ok(31), similarity_score(input));
+        assert_eq!(Err(AocError::parse(2, 1, "expected 2 location IDs, found 1")), pair_distances_from("3   4\n4\n".as_bytes()));
+
+        random::check(100, |rng| {
+            let input = HistorianHysteria::generate(rng, 30);
+            let lists = LocationLists::parse(&input).unwrap();
+            assert_eq!(Ok(lists.distance()), pair_distances(&input));
+            assert_eq!(Ok(lists.similarity()), similarity_score(&input));
         });
-    
-    left.iter().map(|num| right.get(num).unwrap_or(&0) * num).sum()
-}
\ No newline at end of file
+    }
+}
@@ -1,11 +1,59 @@
-mod historian_hysteria;
+//! Day 1: Historian Hysteria, comparing two lists of location IDs.
 
-pub fn part1() {
-    let input = std::str::from_utf8(include_bytes!("../../res/day1/part1")).unwrap();
-    println!("{}", historian_hysteria::pair_distances(input));
+use crate::error::AocError;
+use crate::random::Rng;
+use crate::registry::Part;
+use crate::solution::{Answer, Solution};
+use std::io::BufRead;
+
+pub mod historian_hysteria;
+
+use historian_hysteria::LocationLists;
+
+/// Part 1 sums the distances of the sorted lists, part 2 computes their similarity score.
+pub struct HistorianHysteria;
+
+impl Solution for HistorianHysteria {
+    type Input<'a> = LocationLists;
+
+    /// Sorting and counting while parsing leaves little for either part to do on its own.
+    const COMBINED_TIMING: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        LocationLists::parse(input)
+    }
+
+    fn part1(lists: &Self::Input<'_>) -> Answer {
+        lists.distance().into()
+    }
+
+    fn part2(lists: &Self::Input<'_>) -> Answer {
+        lists.similarity().into()
+    }
+
+    fn stream(reader: &mut dyn BufRead, part: Part) -> Option<Result<Answer, AocError>> {
+        let answer = match part {
+            Part::One => historian_hysteria::pair_distances_from(reader),
+            Part::Two => historian_hysteria::similarity_score_from(reader),
+        };
+        Some(answer.map(Answer::from))
+    }
+
+    /// `size` lines of two five digit location IDs, some of them appearing in both lists.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let left = (0..size).map(|_| rng.range(10000..=99999)).collect::<Vec<_>>();
+        left.iter()
+            .map(|id| {
+                let other = if rng.chance(0.3) { *rng.pick(&left) } else { rng.range(10000..=99999) };
+                format!("{}   {}\n", id, other)
+            })
+            .collect()
+    }
 }
 
-pub fn part2() {
-    let input = std::str::from_utf8(include_bytes!("../../res/day1/part1")).unwrap();;
-    println!("{}", historian_hysteria::similarity_score(input));
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::examples::example_tests!(HistorianHysteria, 1);
+}
@@ -1,86 +1,94 @@
+//! Day 10: Hoof It, scoring the hiking trails of a topographic map.
+
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::random::Rng;
+use crate::solution::{Answer, Solution};
 use std::collections::HashSet;
 
-pub fn part1() {
-    let input = include_str!("../../res/day10/part1");
-    println!("{}", trail_scores(input).0);
-}
+/// Part 1 sums the trailhead scores, part 2 the trailhead ratings.
+pub struct HoofIt;
 
-pub fn part2() {
-    let input = include_str!("../../res/day10/part1");
-    println!("{}", trail_scores(input).1);
-}
+impl Solution for HoofIt {
+    type Input<'a> = Map;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        Map::parse(input)
+    }
 
-struct Map {
-    topology: Vec<Vec<u8>>,
-    width: usize,
-    height: usize,
+    fn part1(map: &Self::Input<'_>) -> Answer {
+        trail_scores(map).0.into()
+    }
+
+    fn part2(map: &Self::Input<'_>) -> Answer {
+        trail_scores(map).1.into()
+    }
+
+    /// A `size` by `size` map of random heights, crossed by up to `size` hiking trails climbing
+    /// from 0 towards 9, which never cross each other.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut heights = Grid::new(size, size, (0..size * size).map(|_| rng.range(0..=9) as u8).collect());
+        let mut carved = HashSet::new();
+        for _ in 0..size {
+            let mut position = Some((rng.index(size), rng.index(size)));
+            for height in 0..=9 {
+                let Some(current) = position.filter(|current| carved.insert(*current)) else { break };
+                heights[current] = height;
+                let neighbors = heights.neighbors4(current).filter(|neighbor| !carved.contains(neighbor)).collect::<Vec<_>>();
+                position = (!neighbors.is_empty()).then(|| *rng.pick(&neighbors));
+            }
+        }
+        heights.map(|height| (b'0' + height) as char).to_string()
+    }
 }
 
-fn trail_scores(input: &str) -> (u32, u32) {
-    let topology = input.lines()
-        .map(|line| line.chars().map(|c| c.to_digit(10).unwrap() as u8).collect::<Vec<_>>())
-        .collect::<Vec<_>>();
+/// The topographic map of heights from 0 to 9.
+pub struct Map {
+    topology: Grid<u8>,
+}
 
-    let map = Map::new(topology);
+/// Returns the summed score and the summed rating of all trailheads. The score counts the distinct
+/// peaks reachable from a trailhead, the rating the number of distinct trails to them.
+pub fn trail_scores(map: &Map) -> (u32, u32) {
+    let trail_scores = map.find_trail_scores();
     (
-        map.find_trail_scores().iter().map(|(a, _)| a).sum(),
-        map.find_trail_scores().iter().map(|(_, b)| b).sum()
+        trail_scores.iter().map(|(a, _)| a).sum(),
+        trail_scores.iter().map(|(_, b)| b).sum()
     )
 }
 
 impl Map {
-    fn new(topology: Vec<Vec<u8>>) -> Map {
-        let width = topology[0].len();
-        let height = topology.len();
-        Map { topology, width, height }
+    /// Parses one row of single digit heights per line.
+    pub fn parse(input: &str) -> Result<Map, AocError> {
+        let topology = Grid::parse(input, |c| c.to_digit(10).map(|height| height as u8).ok_or_else(|| format!("expected a height, found {:?}", c)))?;
+        Ok(Map { topology })
     }
 
     fn find_trail_scores(&self) -> Vec<(u32, u32)> {
         self.topology
-            .iter()
-            .enumerate()
-            .flat_map(|(row, row_vec)| row_vec
-                .iter()
-                .enumerate()
-                .filter(|(col, height)| **height == 0)
-                .map(move |(col, _)| self.scores_for_trail_head((row, col)))
-            )
+            .find_all(|height| *height == 0)
+            .map(|trail_head| self.scores_for_trail_head(trail_head))
             .collect::<Vec<_>>()
     }
 
     fn scores_for_trail_head(&self, trail_head: (usize, usize)) -> (u32, u32) {
-        let result = self.progress_trail(trail_head.0, trail_head.1, 0);
+        let result = self.progress_trail(trail_head, 0);
         (result.0.len() as u32, result.1)
     }
 
-    fn progress_trail(&self, row: usize, col: usize, target_height: u8) -> (HashSet<(usize, usize)>, u32) {
+    fn progress_trail(&self, position: (usize, usize), target_height: u8) -> (HashSet<(usize, usize)>, u32) {
         let mut result_set = HashSet::new();
         let mut found_routes = 0;
-        if self.topology[row][col] != target_height {
+        if self.topology[position] != target_height {
             return (result_set, 0);
         }
         if target_height == 9 {
-            result_set.insert((row, col));
+            result_set.insert(position);
             return (result_set, 1);
         }
-        if row > 0 {
-            let trail_result = self.progress_trail(row - 1, col, target_height + 1);
-            result_set.extend(trail_result.0.into_iter());
-            found_routes += trail_result.1;
-        }
-        if col > 0 {
-            let trail_result = self.progress_trail(row, col - 1, target_height + 1);
-            result_set.extend(trail_result.0.into_iter());
-            found_routes += trail_result.1;
-        }
-        if row < self.height - 1 {
-            let trail_result = self.progress_trail(row + 1, col, target_height + 1);
-            result_set.extend(trail_result.0.into_iter());
-            found_routes += trail_result.1;
-        }
-        if col < self.width - 1 {
-            let trail_result = self.progress_trail(row, col + 1, target_height + 1);
-            result_set.extend(trail_result.0.into_iter());
+        for neighbor in self.topology.neighbors4(position) {
+            let trail_result = self.progress_trail(neighbor, target_height + 1);
+            result_set.extend(trail_result.0);
             found_routes += trail_result.1;
         }
         (result_set, found_routes)
@@ -91,29 +99,10 @@ impl Map {
 mod tests {
     use super::*;
 
-    #[test]
-    fn part1() {
-        let input = r#"89010123
-78121874
-87430965
-96549874
-45678903
-32019012
-01329801
-10456732"#;
-        assert_eq!(36, trail_scores(&input).0);
-    }
+    crate::examples::example_tests!(HoofIt, 10);
 
     #[test]
-    fn part2() {
-        let input = r#"89010123
-78121874
-87430965
-96549874
-45678903
-32019012
-01329801
-10456732"#;
-        assert_eq!(81, trail_scores(&input).1);
+    fn report_malformed_map() {
+        assert_eq!(Some(AocError::parse(2, 2, "expected a height, found '.'")), Map::parse("0123\n1.34").err());
     }
 }
\ No newline at end of file
@@ -1,28 +1,70 @@
+//! Day 11: Plutonian Pebbles, counting stones that change with every blink.
+
+use crate::error::AocError;
+use crate::parsing;
+use crate::random::Rng;
+use crate::solution::{Answer, Solution};
 use std::collections::HashMap;
 
-pub fn part1() {
-    let input = include_str!("../../res/day11/part1");
-    println!("{}", stone_count(input, 25));
-}
+/// Part 1 counts the stones after 25 blinks, part 2 after 75 blinks.
+pub struct PlutonianPebbles;
+
+impl Solution for PlutonianPebbles {
+    type Input<'a> = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        parse_stones(input)
+    }
+
+    fn part1(stones: &Self::Input<'_>) -> Answer {
+        stone_count(stones, 25).into()
+    }
 
-pub fn part2() {
-    let input = include_str!("../../res/day11/part1");
-    println!("{}", stone_count(input, 75));
+    fn part2(stones: &Self::Input<'_>) -> Answer {
+        stone_count(stones, 75).into()
+    }
+
+    /// `size` stones engraved with numbers of up to 7 digits.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let stones = (0..size).map(|_| {
+            let digits = rng.range(1..=7) as u32;
+            rng.range(0..=10u64.pow(digits) - 1).to_string()
+        });
+        stones.collect::<Vec<_>>().join(" ") + "\n"
+    }
 }
 
-fn stone_count(input: &str, num_blinks: u8) -> usize {
-    let stones = input
+/// Stones with more digits could overflow while blinking.
+///
+/// A stone with an odd number of digits grows by 3 or 4 digits when multiplied by 2024. Growing by
+/// 4 leaves a leading digit of 1, so the next multiplication grows it by 3 to an even number of
+/// digits, which splits. Starting from at most 12 digits, no stone ever exceeds 11 + 7 = 18 digits.
+const MAX_DIGITS: usize = 12;
+
+/// Parses the whitespace separated numbers engraved on the stones.
+pub fn parse_stones(input: &str) -> Result<Vec<usize>, AocError> {
+    input
         .split_whitespace()
-        .map(|s| s.parse::<usize>().unwrap())
-        .collect::<Vec<_>>();
+        .map(|s| {
+            let stone = parsing::number::<usize>(input, s)?;
+            if stone.to_string().len() > MAX_DIGITS {
+                return Err(AocError::at(input, s, format!("stone '{}' has more than {} digits", s, MAX_DIGITS)));
+            }
+            Ok(stone)
+        })
+        .collect()
+}
+
+/// Counts the stones after `num_blinks` blinks.
+pub fn stone_count(stones: &[usize], num_blinks: u8) -> usize {
     let mut memory: HashMap<(u8, usize), usize> = HashMap::new();
     simulate_blink(stones, num_blinks, &mut memory)
 }
 
-fn simulate_blink(stones: Vec<usize>, limit: u8, memory: &mut HashMap<(u8, usize), usize>) -> usize {
+fn simulate_blink(stones: &[usize], limit: u8, memory: &mut HashMap<(u8, usize), usize>) -> usize {
     stones
-        .into_iter()
-        .map(|s| simulate_one_stone(s, 0, limit, memory))
+        .iter()
+        .map(|s| simulate_one_stone(*s, 0, limit, memory))
         .sum()
 }
 
@@ -62,10 +104,59 @@ fn split_number(s: String, len: usize) -> (usize, usize) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::random;
+
+    crate::examples::example_tests!(PlutonianPebbles, 11);
+
+    #[test]
+    fn report_malformed_stones() {
+        assert_eq!(Err(AocError::parse(1, 5, "invalid number '1x'")), parse_stones("125 1x"));
+        assert_eq!(
+            Err(AocError::parse(1, 5, "stone '12345678901234567' has more than 12 digits")),
+            parse_stones("125 12345678901234567")
+        );
+    }
+
+    #[test]
+    fn blink_the_largest_stones_without_overflow() {
+        let stones = parse_stones("999999999999 99999999999 49999999999 10000000000 1").unwrap();
+        assert!(stone_count(&stones, 75) > 0);
+    }
+
+    /// Applies the rules to every stone in the row, one blink at a time.
+    fn stone_count_naive(stones: &[usize], num_blinks: u8) -> usize {
+        let mut stones = stones.to_vec();
+        for _ in 0..num_blinks {
+            stones = stones
+                .into_iter()
+                .flat_map(|stone| {
+                    let digits = stone.to_string();
+                    if stone == 0 {
+                        vec![1]
+                    } else if digits.len() % 2 == 0 {
+                        let (left, right) = digits.split_at(digits.len() / 2);
+                        vec![left.parse().unwrap(), right.parse().unwrap()]
+                    } else {
+                        vec![stone * 2024]
+                    }
+                })
+                .collect();
+        }
+        stones.len()
+    }
 
     #[test]
-    fn part1() {
-        let input = "125 17";
-        assert_eq!(55312, stone_count(input, 25));
+    fn stone_count_like_blinking_step_by_step() {
+        random::check(2000, |rng| {
+            let count = rng.range(0..=5);
+            let stones = (0..count)
+                .map(|_| {
+                    let digits = rng.range(1..=7) as u32;
+                    rng.range(0..=10u64.pow(digits)) as usize
+                })
+                .collect::<Vec<_>>();
+            let num_blinks = rng.range(0..=15) as u8;
+            assert_eq!(stone_count_naive(&stones, num_blinks), stone_count(&stones, num_blinks), "{} blinks of {:?}", num_blinks, stones);
+        });
     }
 }
\ No newline at end of file
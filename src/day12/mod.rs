@@ -1,86 +1,93 @@
+//! Day 12: Garden Groups, pricing the fences around garden regions.
+
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::point::Point;
+use crate::random::Rng;
+use crate::solution::{Answer, Solution};
 use std::collections::HashSet;
-use std::usize;
+use std::str::FromStr;
 
-pub fn part1() {
-    let input = include_str!("../../res/day12/part1");
-    println!("{}", fence_price(input));
-}
+/// Part 1 prices fences by perimeter, part 2 by the number of sides.
+pub struct GardenGroups;
 
-pub fn part2() {
-    let input = include_str!("../../res/day12/part1");
-    println!("{}", fence_price_discount(input));
-}
+impl Solution for GardenGroups {
+    type Input<'a> = Grid<char>;
 
-struct Component {
-    name: char,
-    positions: HashSet<Position>,
-    min: Position,
-    max: Position,
-}
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        parse_garden(input)
+    }
 
-#[derive(Debug, PartialEq, Hash, Eq, Clone)]
-struct Position {
-    x: i64,
-    y: i64,
-}
+    fn part1(garden: &Self::Input<'_>) -> Answer {
+        fence_price(garden).into()
+    }
 
-impl From<(i64, i64)> for Position {
-    fn from((x, y): (i64, i64)) -> Self {
-        Position { x, y }
+    fn part2(garden: &Self::Input<'_>) -> Answer {
+        fence_price_discount(garden).into()
     }
+
+    /// A `size` by `size` garden whose plants spread from their neighbours into regions.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let plants = ('A'..='Z').take(rng.range(2..=26) as usize).collect::<Vec<_>>();
+        let mut garden = Grid::new(size, size, (0..size * size).map(|_| *rng.pick(&plants)).collect());
+        for _ in 0..4 {
+            let previous = garden.clone();
+            for position in previous.positions() {
+                let neighbors = previous.neighbors4(position).collect::<Vec<_>>();
+                if !neighbors.is_empty() && rng.chance(0.8) {
+                    garden[position] = previous[*rng.pick(&neighbors)];
+                }
+            }
+        }
+        garden.to_string()
+    }
+}
+
+/// A connected region of garden plots growing the same plant.
+pub struct Component {
+    pub name: char,
+    positions: HashSet<Point>,
+    min: Point,
+    max: Point,
 }
 
 impl Component {
-    fn area(&self) -> u32 {
+    /// The number of plots in the region.
+    pub fn area(&self) -> u32 {
         self.positions.len() as u32
     }
 
-    fn perimeter(&self) -> u32 {
+    /// The number of plot sides bordering a different region or the edge of the map.
+    pub fn perimeter(&self) -> u32 {
         self.positions.iter()
             .fold(0, |perimeter, position| {
-                let neighborhood = position.neighborhood();
-                let uncontained_neighbors = neighborhood.into_iter().filter(|p| !self.positions.contains(&p)).count() as u32;
+                let uncontained_neighbors = position.neighbors4().into_iter().filter(|p| !self.positions.contains(p)).count() as u32;
                 perimeter + uncontained_neighbors
             })
     }
 
-    fn edge_positions(&self) -> HashSet<Position> {
-        self.positions.iter()
-            .filter_map(|p| if p.neighborhood().into_iter().all(|p| self.positions.contains(&p)) {
-                None
-            } else {
-                Some(p.clone())
-            })
-            .collect::<HashSet<_>>()
-    }
-
-    fn edge_segments(&self) -> u32 {
+    /// The number of straight fence sides around the region.
+    pub fn edge_segments(&self) -> u32 {
         let mut segments = 0;
 
         for x in self.min.x..=self.max.x {
             let mut last_left_contained = true;
             let mut last_right_contained = true;
             for y in self.min.y..=self.max.y {
-                let position: Position = (x, y).into();
+                let position = Point::new(x, y);
                 if !self.positions.contains(&position) {
                     last_left_contained = true;
                     last_right_contained = true;
                     continue;
                 }
-                let [left, _, right, _] = position.neighborhood();
+                let [_, right, _, left] = position.neighbors4();
                 let left_contained = self.positions.contains(&left);
-                if left_contained {
-                } else {
-                }
                 if !left_contained && last_left_contained {
                     segments += 1;
                 }
                 last_left_contained = left_contained;
 
                 let right_contained = self.positions.contains(&right);
-                if right_contained {
-                } else {
-                }
                 if !right_contained && last_right_contained {
                     segments += 1;
                 }
@@ -92,27 +99,21 @@ impl Component {
             let mut last_top_contained = true;
             let mut last_bottom_contained = true;
             for x in self.min.x..=self.max.x {
-                let position: Position = (x, y).into();
+                let position = Point::new(x, y);
                 if !self.positions.contains(&position) {
                     last_top_contained = true;
                     last_bottom_contained = true;
                     continue;
                 }
 
-                let [_, top, _, bottom] = position.neighborhood();
+                let [top, _, bottom, _] = position.neighbors4();
                 let top_contained = self.positions.contains(&top);
-                if top_contained {
-                } else {
-                }
                 if !top_contained && last_top_contained {
                     segments += 1;
                 }
                 last_top_contained = top_contained;
 
                 let bottom_contained = self.positions.contains(&bottom);
-                if bottom_contained {
-                } else {
-                }
                 if !bottom_contained && last_bottom_contained {
                     segments += 1;
                 }
@@ -124,52 +125,44 @@ impl Component {
     }
 }
 
-impl Position {
-    fn neighborhood(&self) -> [Position;4] {
-        let x = self.x;
-        let y = self.y;
-        [
-            Position { x: x-1, y      },
-            Position { x,      y: y-1 },
-            Position { x: x+1, y      },
-            Position { x,      y: y+1 },
-        ]
-    }
+/// Parses one row of plant names per line.
+pub fn parse_garden(input: &str) -> Result<Grid<char>, AocError> {
+    Grid::from_str(input)
 }
 
-fn fence_price(input: &str) -> u32 {
-    components(input).into_iter().fold(0, |acc, component| {
+/// Sums area times perimeter of every region.
+pub fn fence_price(board: &Grid<char>) -> u32 {
+    components(board).into_iter().fold(0, |acc, component| {
         let area = component.area();
         let perimeter = component.perimeter();
         acc + area * perimeter
     })
 }
 
-fn fence_price_discount(input: &str) -> u32 {
-    components(input).into_iter().map(|component| {
+/// Sums area times number of sides of every region.
+pub fn fence_price_discount(board: &Grid<char>) -> u32 {
+    components(board).into_iter().map(|component| {
         let segments = component.edge_segments();
         let area = component.area();
         segments * area
     }).sum()
 }
 
-fn components(input: &str) -> Vec<Component> {
+/// Splits the garden into its connected regions.
+pub fn components(board: &Grid<char>) -> Vec<Component> {
     let mut visited = HashSet::new();
     let mut components = Vec::new();
-    let board = input.lines().map(|row| row.chars().collect::<Vec<_>>()).collect::<Vec<_>>();
 
-    for (y, row) in board.iter().enumerate() {
-        for (x, region) in row.iter().enumerate() {
-            if !visited.contains(&(x, y)) {
-                components.push(component(*region, (x, y), &board, &mut visited));
-            }
+    for (position, region) in board.iter() {
+        if !visited.contains(&position) {
+            components.push(component(*region, position, board, &mut visited));
         }
     }
 
     components
 }
 
-fn component(name: char, (x, y): (usize, usize), board: &[Vec<char>], visited: &mut HashSet<(usize, usize)>) -> Component {
+fn component(name: char, (x, y): (usize, usize), board: &Grid<char>, visited: &mut HashSet<(usize, usize)>) -> Component {
     let mut stack = Vec::new();
     let mut positions = HashSet::new();
     let mut min_x = i64::MAX;
@@ -179,7 +172,7 @@ fn component(name: char, (x, y): (usize, usize), board: &[Vec<char>], visited: &
 
     stack.push((x, y));
     while let Some((x, y)) = stack.pop() {
-        if board[y][x] != name {
+        if board[(x, y)] != name {
             continue;
         }
 
@@ -188,58 +181,23 @@ fn component(name: char, (x, y): (usize, usize), board: &[Vec<char>], visited: &
         }
 
         visited.insert((x, y));
-        positions.insert((x as i64, y as i64).into());
+        positions.insert(Point::from((x, y)));
         min_x = min_x.min(x as i64);
         max_x = max_x.max(x as i64);
         min_y = min_y.min(y as i64);
         max_y = max_y.max(y as i64);
 
-        if x > 0 {
-            stack.push((x - 1, y));
-        }
-        if y > 0 {
-            stack.push((x, y - 1));
-        }
-        if x < board[0].len() - 1 {
-            stack.push((x + 1, y));
-        }
-        if y < board.len() - 1 {
-            stack.push((x, y + 1));
-        }
+        stack.extend(board.neighbors4((x, y)));
     }
 
-    let min = (min_x, min_y).into();
-    let max = (max_x, max_y).into();
+    let min = Point::new(min_x, min_y);
+    let max = Point::new(max_x, max_y);
     Component { name, positions, min, max }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day12::{fence_price, fence_price_discount};
-
-    #[test]
-    fn part1() {
-        let input = r#"AAAA
-BBCD
-BBCC
-EEEC"#;
+    use super::*;
 
-        assert_eq!(772, fence_price(input));
-    }
-
-    #[test]
-    fn part2() {
-        let input = r#"RRRRIICCFF
-RRRRIICCCF
-VVRRRCCFFF
-VVRCCCJFFF
-VVVVCJJCFE
-VVIVCCJJEE
-VVIIICJJEE
-MIIIIIJJEE
-MIIISIJEEE
-MMMISSJEEE"#;
-
-        assert_eq!(80, fence_price_discount(input));
-    }
-}
\ No newline at end of file
+    crate::examples::example_tests!(GardenGroups, 12);
+}
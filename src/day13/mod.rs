@@ -1,187 +1,248 @@
-use std::error::Error;
+//! Day 13: Claw Contraption, winning prizes from claw machines with as few tokens as possible.
+
+use std::str::FromStr;
 use winnow::ascii::{digit1, newline};
 use winnow::combinator::{preceded, separated_pair};
 use winnow::error::ContextError;
 use winnow::prelude::*;
 use winnow::Parser;
+use crate::error::AocError;
+use crate::point::Point;
+use crate::random::Rng;
+use crate::solution::{Answer, Solution};
 
-pub fn part1() {
-    let mut input = include_str!("../../res/day13/part1");
-    println!("{}", cheapest_wins(&mut input, false));
-}
-
-pub fn part2() {
-    let mut input = include_str!("../../res/day13/part1");
-    println!("{}", cheapest_wins(&mut input, true));
-}
+/// Part 1 uses the prize positions as given, part 2 moves them by 10000000000000 in both directions.
+pub struct ClawContraption;
 
+impl Solution for ClawContraption {
+    type Input<'a> = Vec<ClawMachine>;
 
-fn cheapest_wins(input: &mut &str, scaled: bool) -> i64 {
-    input.split("\n\n")
-        .map(|mut group| ClawMachine::from(&mut group).cheapest_win(scaled))
-        .sum()
-}
-
-#[derive(Clone, Debug, PartialEq)]
-struct Position {
-    x: i64,
-    y: i64,
-}
-
-struct Line(Position, Position);
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        parse_claw_machines(input)
+    }
 
-struct ClawMachine {
-    button_a: Position,
-    button_b: Position,
-    prize: Position,
-}
+    fn part1(claw_machines: &Self::Input<'_>) -> Answer {
+        cheapest_wins(claw_machines, false).into()
+    }
 
-impl std::ops::Mul<i64> for Position {
-    type Output = Self;
+    fn part2(claw_machines: &Self::Input<'_>) -> Answer {
+        cheapest_wins(claw_machines, true).into()
+    }
 
-    fn mul(self, value: i64) -> Self::Output {
-        Position {
-            x: self.x * value,
-            y: self.y * value,
-        }
+    /// `size` claw machines, about half of whose prizes can be won.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let claw_machines = (0..size).map(|_| {
+            let ClawMachine { button_a, button_b, prize } = ClawMachine::random(rng);
+            format!(
+                "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n",
+                button_a.x, button_a.y, button_b.x, button_b.y, prize.x, prize.y
+            )
+        });
+        claw_machines.collect::<Vec<_>>().join("\n")
     }
 }
 
-impl std::ops::Add for Position {
-    type Output = Self;
+/// Parses the blank line separated claw machine descriptions.
+pub fn parse_claw_machines(input: &str) -> Result<Vec<ClawMachine>, AocError> {
+    input.split("\n\n")
+        .map(|group| ClawMachine::from_str(group).map_err(|error| error.within(input, group)))
+        .collect()
+}
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Position {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
-    }
+/// Sums the fewest tokens needed to win every winnable prize.
+pub fn cheapest_wins(claw_machines: &[ClawMachine], scaled: bool) -> i64 {
+    claw_machines.iter()
+        .map(|claw_machine| claw_machine.cheapest_win(scaled))
+        .sum()
 }
 
-impl std::ops::Sub for Position {
-    type Output = Self;
+struct Line(Point, Point);
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        Position {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-        }
-    }
+/// A claw machine with its two buttons and the position of the prize.
+#[derive(Clone, Copy, Debug)]
+pub struct ClawMachine {
+    button_a: Point,
+    button_b: Point,
+    prize: Point,
 }
 
 impl Line {
-    fn intersection(&self, other: &Self) -> Option<Position> {
-        let a1 = self.1.y - self.0.y;
-        let b1 = self.0.x - self.1.x;
-        let c1 = a1 * self.0.x + b1 * self.0.y;
+    /// `None` if the lines are parallel or the arithmetic overflows.
+    fn intersection(&self, other: &Self) -> Option<Point> {
+        let a1 = self.1.y.checked_sub(self.0.y)?;
+        let b1 = self.0.x.checked_sub(self.1.x)?;
+        let c1 = a1.checked_mul(self.0.x)?.checked_add(b1.checked_mul(self.0.y)?)?;
 
-        let a2 = other.1.y - other.0.y;
-        let b2 = other.0.x - other.1.x;
-        let c2 = a2 * other.0.x + b2 * other.0.y;
+        let a2 = other.1.y.checked_sub(other.0.y)?;
+        let b2 = other.0.x.checked_sub(other.1.x)?;
+        let c2 = a2.checked_mul(other.0.x)?.checked_add(b2.checked_mul(other.0.y)?)?;
 
-        let delta = a1 * b2 - a2 * b1;
+        let delta = a1.checked_mul(b2)?.checked_sub(a2.checked_mul(b1)?)?;
 
         if delta == 0 {
             return None;
         }
 
-        let intersection_x = (b2 * c1 - b1 * c2) / delta;
-        let intersection_y = (a1 * c2 - a2 * c1) / delta;
+        let intersection_x = b2.checked_mul(c1)?.checked_sub(b1.checked_mul(c2)?)? / delta;
+        let intersection_y = a1.checked_mul(c2)?.checked_sub(a2.checked_mul(c1)?)? / delta;
 
-        Some(Position {
-            x: intersection_x,
-            y: intersection_y,
-        })
+        Some(Point::new(intersection_x, intersection_y))
     }
 }
 
-impl From<&mut &str> for ClawMachine {
-    fn from(input: &mut &str) -> Self {
-        fn parse_button(input: &mut &str, button_name: &str) -> PResult<Position> {
-            preceded(format!("Button {}: ", button_name).as_str(), separated_pair(preceded("X+", digit1), ", ", preceded("Y+", digit1)))
+/// How often `button` has to be pressed to move the claw by `distance`, dividing by whichever of
+/// its coordinates is not 0. The caller checks that the presses actually reach `distance`.
+fn presses(distance: Point, button: Point) -> Option<i64> {
+    distance.x.checked_div(button.x).or_else(|| distance.y.checked_div(button.y))
+}
+
+impl FromStr for ClawMachine {
+    type Err = AocError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        fn parse_button(input: &mut &str, button_name: &str) -> PResult<Point> {
+            preceded(format!("Button {}: ", button_name).as_str(), separated_pair(preceded("X+", digit1.parse_to()), ", ", preceded("Y+", digit1.parse_to())))
                 .parse_next(input)
-                .map(|(x, y)| Position { x: x.parse::<i64>().unwrap(), y: y.parse::<i64>().unwrap() })
+                .map(|(x, y)| Point::new(x, y))
         }
 
-        fn parse_prize(input: &mut &str) -> PResult<Position> {
-            preceded("Prize: ", separated_pair(preceded("X=", digit1), ", ", preceded("Y=", digit1)))
+        fn parse_prize(input: &mut &str) -> PResult<Point> {
+            preceded("Prize: ", separated_pair(preceded("X=", digit1.parse_to()), ", ", preceded("Y=", digit1.parse_to())))
                 .parse_next(input)
-                .map(|(x, y)| Position { x: x.parse::<i64>().unwrap(), y: y.parse::<i64>().unwrap() })
+                .map(|(x, y)| Point::new(x, y))
         }
 
-        let button_a = parse_button(input, "A").unwrap();
-        newline::<&str, ContextError>.parse_next(input).unwrap();
-        let button_b = parse_button(input, "B").unwrap();
-        newline::<&str, ContextError>.parse_next(input).unwrap();
-        let prize = parse_prize(input).unwrap();
+        let mut remaining = input;
+        let expected = |remaining: &str, expected: &str| AocError::at(input, remaining, format!("expected {}", expected));
 
-        Self { button_a, button_b, prize }
+        let button_a = parse_button(&mut remaining, "A").map_err(|_| expected(remaining, "'Button A: X+<number>, Y+<number>'"))?;
+        newline::<&str, ContextError>.parse_next(&mut remaining).map_err(|_| expected(remaining, "a line break"))?;
+        let button_b = parse_button(&mut remaining, "B").map_err(|_| expected(remaining, "'Button B: X+<number>, Y+<number>'"))?;
+        newline::<&str, ContextError>.parse_next(&mut remaining).map_err(|_| expected(remaining, "a line break"))?;
+        let prize = parse_prize(&mut remaining).map_err(|_| expected(remaining, "'Prize: X=<number>, Y=<number>'"))?;
+        if !remaining.trim_end().is_empty() {
+            return Err(expected(remaining, "a blank line after the prize"));
+        }
+
+        Ok(Self { button_a, button_b, prize })
     }
 }
 
 impl ClawMachine {
-    fn cheapest_win(&self, scaled: bool) -> i64 {
-        let prize = if scaled { self.prize.clone() + Position { x: 10000000000000, y: 10000000000000 } } else { self.prize.clone() };
-
-        let line_a = Line(Position { x: 0, y: 0 }, self.button_a.clone());
-        let line_b = Line(prize.clone() - self.button_b.clone(), prize.clone());
-
-        line_a.intersection(&line_b)
-            .map(|intersection_point| {
-                let multiplier_a = intersection_point.x / self.button_a.x;
-                let multiplier_b = (prize.x - multiplier_a * self.button_a.x) / self.button_b.x;
-
-                if self.button_a.clone() * multiplier_a +  self.button_b.clone() * multiplier_b != prize {
-                    0
-                } else {
-                    3 * multiplier_a + multiplier_b
-                }
-            })
+    /// Returns the tokens needed to win the prize, where pressing button A costs 3 tokens and
+    /// button B costs 1 token, or 0 if the prize cannot be won.
+    pub fn cheapest_win(&self, scaled: bool) -> i64 {
+        self.cheapest_presses(scaled)
+            .and_then(|(multiplier_a, multiplier_b)| multiplier_a.checked_mul(3)?.checked_add(multiplier_b))
             .unwrap_or(0)
     }
+
+    /// The presses of both buttons that win the prize, or `None` if it cannot be won, including
+    /// when the arithmetic overflows.
+    fn cheapest_presses(&self, scaled: bool) -> Option<(i64, i64)> {
+        let prize = if scaled { self.prize.checked_add(Point::new(10000000000000, 10000000000000))? } else { self.prize };
+
+        let line_a = Line(Point::ORIGIN, self.button_a);
+        let line_b = Line(prize.checked_sub(self.button_b)?, prize);
+        let intersection_point = line_a.intersection(&line_b)?;
+
+        let multiplier_a = presses(intersection_point, self.button_a)?;
+        let moved_a = self.button_a.checked_mul(multiplier_a)?;
+        let multiplier_b = presses(prize.checked_sub(moved_a)?, self.button_b)?;
+
+        let reached = moved_a.checked_add(self.button_b.checked_mul(multiplier_b)?)?;
+        (multiplier_a >= 0 && multiplier_b >= 0 && reached == prize).then_some((multiplier_a, multiplier_b))
+    }
+
+    /// A machine whose prize is reachable half of the time. Like in the puzzle inputs, buttons
+    /// never move the claw in the same direction, so there is at most one way to win.
+    fn random(rng: &mut Rng) -> ClawMachine {
+        let mut button = || Point::new(rng.range(1..=99) as i64, rng.range(1..=99) as i64);
+        let (button_a, button_b) = loop {
+            let (a, b) = (button(), button());
+            if a.normalized() != b.normalized() {
+                break (a, b);
+            }
+        };
+        let prize = if rng.chance(0.5) {
+            button_a * rng.range(0..=100) as i64 + button_b * rng.range(0..=100) as i64
+        } else {
+            Point::new(rng.range(0..=20000) as i64, rng.range(0..=20000) as i64)
+        };
+        ClawMachine { button_a, button_b, prize }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day13::cheapest_wins;
+    use super::*;
+    use crate::random;
+
+    crate::examples::example_tests!(ClawContraption, 13);
 
     #[test]
-    fn part1() {
-        let mut input = "Button A: X+94, Y+34
+    fn report_malformed_claw_machine() {
+        let input = "Button A: X+94, Y+34
 Button B: X+22, Y+67
 Prize: X=8400, Y=5400
 
 Button A: X+26, Y+66
-Button B: X+67, Y+21
-Prize: X=12748, Y=12176
-
-Button A: X+17, Y+86
-Button B: X+84, Y+37
-Prize: X=7870, Y=6450
-
-Button A: X+69, Y+23
-Button B: X+27, Y+71
-Prize: X=18641, Y=10279";
-        assert_eq!(480, cheapest_wins(&mut input, false));
+Button B: X+67 Y+21
+Prize: X=12748, Y=12176";
+        assert_eq!(
+            Some(AocError::parse(6, 15, "expected 'Button B: X+<number>, Y+<number>'")),
+            parse_claw_machines(input).err()
+        );
     }
 
     #[test]
-    fn part2() {
-        let mut input = "Button A: X+94, Y+34
-Button B: X+22, Y+67
-Prize: X=8400, Y=5400
+    fn win_with_buttons_that_do_not_move_along_x() {
+        let claw_machine = ClawMachine { button_a: Point::new(0, 5), button_b: Point::new(3, 1), prize: Point::new(6, 12) };
+        assert_eq!(8, claw_machine.cheapest_win(false));
+        let claw_machine = ClawMachine { button_a: Point::new(3, 1), button_b: Point::new(0, 5), prize: Point::new(6, 12) };
+        assert_eq!(8, claw_machine.cheapest_win(false));
+        let claw_machine = ClawMachine { button_a: Point::new(0, 2), button_b: Point::new(0, 3), prize: Point::new(0, 12) };
+        assert_eq!(0, claw_machine.cheapest_win(false));
+        let claw_machine = ClawMachine { button_a: Point::ORIGIN, button_b: Point::new(3, 1), prize: Point::new(6, 2) };
+        assert_eq!(0, claw_machine.cheapest_win(false));
+    }
 
-Button A: X+26, Y+66
-Button B: X+67, Y+21
-Prize: X=12748, Y=12176
+    #[test]
+    fn treat_overflow_as_not_winnable() {
+        let claw_machine = ClawMachine { button_a: Point::new(94, 34), button_b: Point::new(22, 67), prize: Point::new(i64::MAX, i64::MAX) };
+        assert_eq!(0, claw_machine.cheapest_win(false));
+        assert_eq!(0, claw_machine.cheapest_win(true));
+        let claw_machine = ClawMachine { button_a: Point::new(i64::MAX, 1), button_b: Point::new(1, i64::MAX), prize: Point::new(8400, 5400) };
+        assert_eq!(0, claw_machine.cheapest_win(false));
+        let claw_machine = ClawMachine { button_a: Point::new(1, 0), button_b: Point::new(0, 1), prize: Point::new(i64::MAX / 2, 1) };
+        assert_eq!(0, claw_machine.cheapest_win(false));
+    }
 
-Button A: X+17, Y+86
-Button B: X+84, Y+37
-Prize: X=7870, Y=6450
+    /// Tries every number of presses of button A.
+    fn cheapest_win_naive(claw_machine: &ClawMachine) -> i64 {
+        let ClawMachine { button_a, button_b, prize } = *claw_machine;
+        (0..=prize.x / button_a.x)
+            .filter_map(|presses_a| {
+                let rest = prize - button_a * presses_a;
+                let presses_b = rest.x / button_b.x;
+                (button_b * presses_b == rest).then_some(3 * presses_a + presses_b)
+            })
+            .min()
+            .unwrap_or(0)
+    }
 
-Button A: X+69, Y+23
-Button B: X+27, Y+71
-Prize: X=18641, Y=10279";
-        assert_eq!(480, cheapest_wins(&mut input, true));
+    #[test]
+    fn cheapest_win_like_trying_all_presses() {
+        random::check(2000, |rng| {
+            let claw_machine = ClawMachine::random(rng);
+            assert_eq!(
+                cheapest_win_naive(&claw_machine),
+                claw_machine.cheapest_win(false),
+                "buttons {:?} and {:?}, prize {:?}",
+                claw_machine.button_a,
+                claw_machine.button_b,
+                claw_machine.prize
+            );
+        });
     }
-}
\ No newline at end of file
+}
@@ -1,11 +1,56 @@
-mod red_nodes_reports;
+//! Day 2: Red-Nosed Reports, checking reactor level reports for safety.
 
-pub fn part1() {
-    let input = std::str::from_utf8(include_bytes!("../../res/day2/part1")).unwrap();;
-    println!("{}", red_nodes_reports::safe_reports(input, false));
+use crate::error::AocError;
+use crate::random::Rng;
+use crate::registry::Part;
+use crate::solution::{Answer, Solution};
+use std::io::BufRead;
+
+pub mod red_nodes_reports;
+
+use red_nodes_reports::SafetyPolicy;
+
+/// Part 1 counts the safe reports, part 2 also accepts reports made safe by removing one level.
+pub struct RedNosedReports;
+
+impl Solution for RedNosedReports {
+    type Input<'a> = Vec<Vec<i32>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        red_nodes_reports::parse_reports(input)
+    }
+
+    fn part1(reports: &Self::Input<'_>) -> Answer {
+        SafetyPolicy::STRICT.count_safe(reports).into()
+    }
+
+    fn part2(reports: &Self::Input<'_>) -> Answer {
+        SafetyPolicy::DAMPENED.count_safe(reports).into()
+    }
+
+    fn stream(reader: &mut dyn BufRead, part: Part) -> Option<Result<Answer, AocError>> {
+        let policy = match part {
+            Part::One => SafetyPolicy::STRICT,
+            Part::Two => SafetyPolicy::DAMPENED,
+        };
+        Some(red_nodes_reports::safe_reports_from(reader, &policy).map(Answer::from))
+    }
+
+    /// `size` reports of 5 to 8 levels, mostly changing gradually with an occasional bad step.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                let length = rng.range(5..=8) as usize;
+                let report = red_nodes_reports::generate_report(rng, length, 0.1);
+                report.iter().map(i32::to_string).collect::<Vec<_>>().join(" ") + "\n"
+            })
+            .collect()
+    }
 }
 
-pub fn part2() {
-    let input = std::str::from_utf8(include_bytes!("../../res/day2/part1")).unwrap();;
-    println!("{}", red_nodes_reports::safe_reports(input, true));
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::examples::example_tests!(RedNosedReports, 2);
+}
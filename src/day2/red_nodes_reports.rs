@@ -1,62 +1,241 @@
 
-pub fn safe_reports(input: &str, with_tolerance: bool) -> u32 {
-    input
-        .lines()
-        .map(|line| line
-            .split_whitespace()
-            .map(|number| number.parse::<i32>().unwrap())
-            .collect::<Vec<i32>>())
-        .filter(|report| {
-            if with_tolerance {
-                check_report_with_tolerance(report)
-            } else  {
-                check_report(report)
-            }
-        })
-        .count() as u32
+use crate::error::AocError;
+use crate::parsing;
+use crate::random::Rng;
+use std::io::BufRead;
+
+/// Parses one report of whitespace separated levels per line.
+pub fn parse_reports(input: &str) -> Result<Vec<Vec<i32>>, AocError> {
+    let mut reports = Vec::new();
+    parsing::read_lines(input.as_bytes(), |line| {
+        reports.push(parse_report(line)?);
+        Ok(())
+    })?;
+    Ok(reports)
 }
 
-fn check_report(report: &[i32]) -> bool {
-    let descending = report[0] - report[1] > 0;
-    report.windows(2)
-        .map(|pair| pair[0] - pair[1])
-        .all(|diff| if descending {
-            diff > 0 && diff >= 1 && diff <= 3
-        } else {
-            diff < 0 && diff <= -1 && diff >= -3
-        })
+/// Parses the whitespace separated levels of a single report.
+pub fn parse_report(line: &str) -> Result<Vec<i32>, AocError> {
+    let report = line
+        .split_whitespace()
+        .map(|number| parsing::number::<i32>(line, number))
+        .collect::<Result<Vec<i32>, _>>()?;
+    if report.is_empty() {
+        return Err(AocError::at(line, line, "empty report"));
+    }
+    Ok(report)
+}
+
+/// Counts the reports that are safe under `policy`.
+pub fn safe_reports(input: &str, policy: &SafetyPolicy) -> Result<u64, AocError> {
+    safe_reports_from(input.as_bytes(), policy)
+}
+
+/// [`safe_reports`] for reports read line by line from `reader`, holding one report at a time.
+pub fn safe_reports_from(reader: impl BufRead, policy: &SafetyPolicy) -> Result<u64, AocError> {
+    let mut safe = 0;
+    parsing::read_lines(reader, |line| {
+        safe += policy.is_safe(&parse_report(line)?) as u64;
+        Ok(())
+    })?;
+    Ok(safe)
+}
+
+/// Which way the levels of a safe report may change.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Direction {
+    Increasing,
+    Decreasing,
+    /// Increasing or decreasing, as long as the whole report goes the same way.
+    Either,
+}
+
+/// The rules a report has to follow to be safe.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SafetyPolicy {
+    /// The smallest allowed difference between adjacent levels. With 0, equal levels fit any
+    /// direction.
+    pub min_step: u32,
+    /// The largest allowed difference between adjacent levels.
+    pub max_step: u32,
+    pub direction: Direction,
+    /// How many levels may at most be removed to make a report safe.
+    pub removable: usize,
 }
 
-fn check_report_with_tolerance(report: &Vec<i32>) -> bool {
-    if check_report(&report) {
-        return true;
+impl SafetyPolicy {
+    /// Levels strictly increasing or decreasing by steps of 1 to 3.
+    pub const STRICT: SafetyPolicy = SafetyPolicy { min_step: 1, max_step: 3, direction: Direction::Either, removable: 0 };
+
+    /// [`STRICT`](Self::STRICT), but tolerating a single bad level.
+    pub const DAMPENED: SafetyPolicy = SafetyPolicy { removable: 1, ..SafetyPolicy::STRICT };
+
+    /// Counts the reports that are safe under the policy.
+    pub fn count_safe(&self, reports: &[Vec<i32>]) -> u64 {
+        reports.iter().filter(|report| self.is_safe(report)).count() as u64
+    }
+
+    /// Whether `report` follows the policy after removing at most [`removable`](Self::removable)
+    /// levels. Reports of fewer than two levels are always safe.
+    pub fn is_safe(&self, report: &[i32]) -> bool {
+        match self.direction {
+            Direction::Either => self.is_safe_going(report, Direction::Increasing) || self.is_safe_going(report, Direction::Decreasing),
+            direction => self.is_safe_going(report, direction),
+        }
+    }
+
+    fn is_safe_going(&self, report: &[i32], direction: Direction) -> bool {
+        let allows = |lhs, rhs| self.allows_step(lhs, rhs, direction);
+        let Some(bad) = report.windows(2).position(|pair| !allows(pair[0], pair[1])) else { return true };
+        match self.removable {
+            0 => false,
+            // The steps before the first bad one are fine, so one of its levels has to go.
+            1 => [bad, bad + 1].into_iter().any(|removed| steps_without(report, removed).all(|(lhs, rhs)| allows(lhs, rhs))),
+            removable => self.fewest_removals(report, direction) <= removable,
+        }
     }
-    
-    for i in 0..report.len() {
-        let mut cloned_report = report.clone();
-        cloned_report.remove(i);
-        if check_report(&cloned_report) {
-            return true;
+
+    /// The fewest levels to remove from `report` so that it goes `direction`, never removing more
+    /// than [`removable`](Self::removable) levels in a row, which takes `O(n * removable)` time.
+    fn fewest_removals(&self, report: &[i32], direction: Direction) -> usize {
+        let window = self.removable + 1;
+        // `removals[i]` is the fewest levels removed before level `i` when keeping it as the last
+        // level so far. Removing all of them is always possible.
+        let mut removals = Vec::with_capacity(report.len());
+        for (i, &level) in report.iter().enumerate() {
+            let fewest = (i.saturating_sub(window)..i)
+                .filter(|&j| self.allows_step(report[j], level, direction))
+                .map(|j| removals[j] + i - j - 1)
+                .fold(i, usize::min);
+            removals.push(fewest);
         }
+        removals.iter().enumerate().map(|(i, removed)| removed + report.len() - 1 - i).min().unwrap_or(0)
+    }
+
+    /// Whether going from level `lhs` to level `rhs` is allowed when the report goes `direction`.
+    fn allows_step(&self, lhs: i32, rhs: i32, direction: Direction) -> bool {
+        let step = rhs as i64 - lhs as i64;
+        let right_way = match direction {
+            Direction::Increasing => step >= 0,
+            Direction::Decreasing => step <= 0,
+            Direction::Either => true,
+        };
+        right_way && (self.min_step as u64..=self.max_step as u64).contains(&step.unsigned_abs())
     }
-    
-    false
+}
+
+/// The adjacent levels of `report` once the level at `removed` is left out.
+fn steps_without(report: &[i32], removed: usize) -> impl Iterator<Item = (i32, i32)> + '_ {
+    let levels = move || report.iter().enumerate().filter(move |(index, _)| *index != removed).map(|(_, level)| *level);
+    levels().zip(levels().skip(1))
+}
+
+/// A report of `length` levels changing gradually, except for steps that are bad with probability
+/// `bad_steps`.
+pub fn generate_report(rng: &mut Rng, length: usize, bad_steps: f64) -> Vec<i32> {
+    let direction = if rng.chance(0.5) { 1 } else { -1 };
+    let mut level = rng.range(50..=99) as i32;
+    let mut levels = vec![level];
+    for _ in 1..length {
+        let step = if rng.chance(bad_steps) { *rng.pick(&[0, 4, 5, -2]) } else { rng.range(1..=3) as i32 };
+        level += direction * step;
+        levels.push(level);
+    }
+    levels
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day2::red_nodes_reports::safe_reports;
+    use super::*;
+    use crate::bench;
+    use crate::random;
+    use std::time::Instant;
+
+    #[test]
+    fn short_reports() {
+        let reports = parse_reports("1 9\n4\n").unwrap();
+        assert_eq!(1, SafetyPolicy::STRICT.count_safe(&reports));
+        assert_eq!(2, SafetyPolicy::DAMPENED.count_safe(&reports));
+        assert_eq!(Ok(2), safe_reports("1 9\n4\n", &SafetyPolicy::DAMPENED));
+        assert!(parse_reports("1 2\n\n3 4").is_err());
+    }
+
+    #[test]
+    fn stream_reports() {
+        let input = "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9\n";
+        assert_eq!(Ok(2), safe_reports_from(input.as_bytes(), &SafetyPolicy::STRICT));
+        assert_eq!(Ok(4), safe_reports_from(input.as_bytes(), &SafetyPolicy::DAMPENED));
+        assert_eq!(Err(AocError::parse(2, 3, "invalid number 'x'")), safe_reports_from("1 2\n3 x\n".as_bytes(), &SafetyPolicy::DAMPENED));
+    }
 
     #[test]
-    fn test_report() {
-        let input = r#"7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9
-"#;
-        assert_eq!(2, safe_reports(input, false));
-        assert_eq!(4, safe_reports(input, true));
+    fn custom_policies() {
+        let policy = |min_step, max_step, direction, removable| SafetyPolicy { min_step, max_step, direction, removable };
+        assert!(policy(1, 3, Direction::Increasing, 0).is_safe(&[1, 2, 4, 7]));
+        assert!(!policy(1, 3, Direction::Decreasing, 0).is_safe(&[1, 2, 4, 7]));
+        assert!(policy(1, 3, Direction::Decreasing, 0).is_safe(&[7, 4, 2, 1]));
+        assert!(!policy(1, 2, Direction::Either, 0).is_safe(&[1, 2, 4, 7]));
+        assert!(policy(0, 3, Direction::Either, 0).is_safe(&[1, 1, 4, 4]));
+        assert!(!policy(0, 3, Direction::Either, 0).is_safe(&[1, 1, 4, 3]));
+        assert!(policy(2, 5, Direction::Either, 0).is_safe(&[10, 5, 3]));
+
+        let report = [1, 9, 2, 8, 3, 4];
+        assert!(!policy(1, 3, Direction::Either, 1).is_safe(&report));
+        assert!(policy(1, 3, Direction::Either, 2).is_safe(&report));
+        assert!(!policy(1, 3, Direction::Decreasing, 3).is_safe(&report));
+        assert!(policy(1, 3, Direction::Decreasing, 4).is_safe(&report));
+        assert!(policy(1, 3, Direction::Increasing, 0).is_safe(&[5]));
+    }
+
+    /// Removes every level in turn from a copy of the report, as many times as allowed.
+    fn is_safe_naive(policy: &SafetyPolicy, report: &[i32]) -> bool {
+        let strict = SafetyPolicy { removable: 0, ..*policy };
+        strict.is_safe(report) || policy.removable > 0 && (0..report.len()).any(|index| {
+            let mut cloned_report = report.to_vec();
+            cloned_report.remove(index);
+            is_safe_naive(&SafetyPolicy { removable: policy.removable - 1, ..*policy }, &cloned_report)
+        })
+    }
+
+    #[test]
+    fn remove_levels_like_trying_every_removal() {
+        let directions = [Direction::Increasing, Direction::Decreasing, Direction::Either];
+        random::check(2000, |rng| {
+            let min_step = rng.range(0..=2) as u32;
+            let policy = SafetyPolicy {
+                min_step,
+                max_step: min_step + rng.range(0..=3) as u32,
+                direction: *rng.pick(&directions),
+                removable: rng.range(0..=3) as usize,
+            };
+            let length = rng.range(0..=9) as usize;
+            let report = if rng.chance(0.5) {
+                generate_report(rng, length, 0.1)
+            } else {
+                (0..length).map(|_| rng.range(0..=10) as i32).collect()
+            };
+            assert_eq!(is_safe_naive(&policy, &report), policy.is_safe(&report), "{:?} {:?}", policy, report);
+        });
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with --release -- --ignored --nocapture"]
+    fn bench_long_reports() {
+        let mut rng = Rng::new(2);
+        let reports = (0..20).map(|_| generate_report(&mut rng, 5000, 0.0003)).collect::<Vec<_>>();
+        let time = |name, safe_reports: &dyn Fn(&[i32]) -> bool| {
+            let start = Instant::now();
+            let safe = reports.iter().filter(|report| safe_reports(report)).count();
+            println!("{:<20} {} safe in {}", name, safe, bench::format_duration(start.elapsed()));
+            safe
+        };
+        for removable in 1..=2 {
+            let policy = SafetyPolicy { removable, ..SafetyPolicy::STRICT };
+            println!("{} removable levels:", removable);
+            let safe = time("without cloning", &|report| policy.is_safe(report));
+            if removable == 1 {
+                assert_eq!(safe, time("cloning", &|report| is_safe_naive(&policy, report)));
+            }
+        }
     }
 }
\ No newline at end of file
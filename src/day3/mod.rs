@@ -1,11 +1,59 @@
-mod mull_it_over;
+//! Day 3: Mull It Over, recovering multiplications from corrupted memory.
 
-pub fn part1() {
-    let mut input = std::str::from_utf8(include_bytes!("../../res/day3/part1")).unwrap();
-    println!("{}", mull_it_over::compute_multiplications(&mut input));
+use crate::error::AocError;
+use crate::random::Rng;
+use crate::solution::{Answer, Solution};
+
+pub mod mull_it_over;
+
+/// Part 1 sums all multiplications, part 2 honors the `do()` and `don't()` instructions.
+pub struct MullItOver;
+
+impl Solution for MullItOver {
+    type Input<'a> = Vec<mull_it_over::Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        Ok(mull_it_over::parse_instructions(input))
+    }
+
+    fn part1(instructions: &Self::Input<'_>) -> Answer {
+        mull_it_over::compute_multiplications(instructions, false).into()
+    }
+
+    fn part2(instructions: &Self::Input<'_>) -> Answer {
+        mull_it_over::compute_multiplications(instructions, true).into()
+    }
+
+    /// `size` instructions and pieces of garbage, some of which look almost like instructions.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        const GARBAGE: [&str; 14] = ["what()", "from()", "where(1,2)", "how()", "mul[3,7]", "mul(4*", "mul ( 2 , 4 )", "don't", "do(", "?", "%", "~", "'", "mul(12,"];
+        let mut memory = String::new();
+        for _ in 0..size {
+            match rng.range(0..=5) {
+                0 => memory.push_str("do()"),
+                1 => memory.push_str("don't()"),
+                2 | 3 => memory.push_str(&format!("mul({},{})", rng.range(1..=999), rng.range(1..=999))),
+                _ => memory.push_str(GARBAGE[rng.index(GARBAGE.len())]),
+            }
+            if rng.chance(0.05) {
+                memory.push('\n');
+            }
+        }
+        memory + "\n"
+    }
 }
 
-// pub fn part2() {
-//     let input = std::str::from_utf8(include_bytes!("../../res/day1/part1")).unwrap();;
-//     println!("{}", crate::day3::historian_hysteria::similarity_score(input));
-// }
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::examples::example_tests!(MullItOver, 3);
+
+    #[test]
+    fn solve_large_generated_input() {
+        let input = MullItOver::generate(&mut Rng::new(3), 100_000);
+        let instructions = MullItOver::parse(&input).unwrap();
+        let Answer::Unsigned(sum) = MullItOver::part1(&instructions) else { panic!("expected an unsigned answer") };
+        assert!(sum > u32::MAX as u64);
+    }
+}
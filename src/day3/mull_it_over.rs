@@ -2,31 +2,47 @@ use winnow::ascii::digit1;
 use winnow::combinator::{alt, preceded, separated_pair, terminated};
 use winnow::{PResult, Parser};
 
-enum Instruction {
+/// An instruction recovered from the corrupted memory.
+pub enum Instruction {
     Multiple(u32, u32),
     Do,
     Dont
 }
 
-pub fn compute_multiplications(input: &str) -> u32 {
+/// Extracts all valid instructions, skipping over any corrupted characters in between.
+pub fn parse_instructions(input: &str) -> Vec<Instruction> {
     let mut input = input;
+    let mut instructions = Vec::new();
+    while !input.is_empty() {
+        match parse_instruction(&mut input) {
+            Ok(instruction) => instructions.push(instruction),
+            Err(_) => {
+                let skipped = input.chars().next().map(char::len_utf8).unwrap_or(1);
+                input = &input[skipped..];
+            }
+        }
+    }
+
+    instructions
+}
+
+/// Sums the products of all multiplications. If `conditional` is set, multiplications following a
+/// `don't()` are ignored until the next `do()`.
+pub fn compute_multiplications(instructions: &[Instruction], conditional: bool) -> u64 {
     let mut tuples = Vec::new();
     let mut enabled = true;
-    while input.len() > 0 {
-        match parse_instruction(&mut input) {
-            Ok(instruction) => match instruction {
-                Instruction::Do => enabled = true,
-                Instruction::Dont => enabled = false,
-                Instruction::Multiple(x, y) if enabled => {
-                    tuples.push((x, y));
-                }
-                _ => ()
-            },
-            Err(_) => input = &mut &input[1..],
+    for instruction in instructions {
+        match instruction {
+            Instruction::Do => enabled = true,
+            Instruction::Dont => enabled = !conditional,
+            Instruction::Multiple(x, y) if enabled => {
+                tuples.push((x, y));
+            }
+            _ => ()
         }
     }
 
-    tuples.into_iter().map(|(lhs, rhs)| lhs * rhs).sum()
+    tuples.into_iter().map(|(lhs, rhs)| *lhs as u64 * *rhs as u64).sum()
 }
 
 fn parse_instruction(input: &mut &str) -> PResult<Instruction> {
@@ -42,15 +58,15 @@ fn parse_mul_tuple(input: &mut &str) -> PResult<Instruction> {
         preceded(
             "mul(", 
             separated_pair(
-                digit1, 
+                digit1.parse_to::<u32>(), 
                 ',', 
-                digit1
+                digit1.parse_to::<u32>()
             )
         ),
         ')'
     )
         .parse_next(input)
-        .map(|(lhs, rhs)| Instruction::Multiple(lhs.parse::<u32>().unwrap(), rhs.parse::<u32>().unwrap()))
+        .map(|(lhs, rhs)| Instruction::Multiple(lhs, rhs))
 }
 
 fn parse_do(input: &mut &str) -> PResult<Instruction> {
@@ -63,10 +79,17 @@ fn parse_dont(input: &mut &str) -> PResult<Instruction> {
 
 #[cfg(test)]
 mod tests {
-    use crate::day3::mull_it_over::compute_multiplications;
+    use crate::day3::mull_it_over::{compute_multiplications, parse_instructions};
+
+    #[test]
+    fn skip_invalid_instructions() {
+        let instructions = parse_instructions("mul(99999999999,2)ümul(3,4)");
+        assert_eq!(12, compute_multiplications(&instructions, false));
+    }
 
     #[test]
-    fn test() {
-        assert_eq!(48, compute_multiplications(&mut "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))"));
+    fn sum_beyond_u32() {
+        let instructions = parse_instructions(&"mul(999,999)".repeat(5000));
+        assert_eq!(5000 * 999 * 999, compute_multiplications(&instructions, true));
     }
 }
@@ -1,222 +1,96 @@
-use winnow::Parser;
+//! Day 4: Ceres Search, a word search for `XMAS`.
 
-pub fn part1() {
-    let mut input = std::str::from_utf8(include_bytes!("../../res/day4/part1")).unwrap();
-    println!("{}", word_search(&mut input));
-}
-
-pub fn part2() {
-    let mut input = std::str::from_utf8(include_bytes!("../../res/day4/part1")).unwrap();
-    println!("{}", x_search(&mut input));
-}
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::point::Point;
+use crate::random::Rng;
+use crate::solution::{Answer, Solution};
+use std::str::FromStr;
 
-struct Board<'a> {
-    board: Vec<Vec<&'a str>>,
-    width: usize,
-    height: usize,
-}
+/// Part 1 counts every `XMAS` in the grid, part 2 counts the `MAS` crosses.
+pub struct CeresSearch;
 
-struct XmasKernel {
-    size: usize,
-    target: String
-}
+impl Solution for CeresSearch {
+    type Input<'a> = Grid<char>;
 
-struct XKernel {
-    size: usize,
-}
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        Grid::from_str(input)
+    }
 
-impl XKernel {
-    pub(crate) fn search(&self, board: &[Vec<&str>], x: usize, y: usize) -> bool {
-        if board[y][x] != "A" {
-            return false;
-        }
-        
-        if ((board[y-1][x-1] == "M" && board[y+1][x+1] == "S") || (board[y-1][x-1] == "S" && board[y+1][x+1] == "M")) &&
-            ((board[y+1][x-1] == "M" && board[y-1][x+1] == "S") || (board[y+1][x-1] == "S" && board[y-1][x+1] == "M")) {
-            return true;
-        }
-        
-        false
+    fn part1(board: &Self::Input<'_>) -> Answer {
+        word_search(board).into()
     }
-}
 
-impl XmasKernel {
-    fn new (target: String) -> Self {
-        XmasKernel {
-            size: target.len(),
-            target
-        }
+    fn part2(board: &Self::Input<'_>) -> Answer {
+        x_search(board).into()
     }
-}
 
-fn word_search(input: &str) -> u32 {
-    let board = Board::parse(input);
-    board.accept(XmasKernel::new("XMAS".to_string()))
+    /// A `size` by `size` grid of the letters X, M, A and S.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let cells = (0..size * size).map(|_| *rng.pick(&['X', 'M', 'A', 'S'])).collect();
+        Grid::new(size, size, cells).to_string()
+    }
 }
 
-fn x_search(input: &str) -> u32 {
-    let board = Board::parse(input);
-    board.accept2(XKernel { size: 3 })
+struct XmasKernel {
+    target: Vec<char>
 }
 
-impl<'a> Board<'a> {
-    fn parse(input: &'a str) -> Self {
-        let board = input
-            .lines()
-            .map(|line| (0..line.len()).map(|index| &line[index..index+1]).collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-        let width = board[0].len();
-        let height = board.len();
-
-        Board { board, width, height }
-    }
-
-    fn accept2(&self, kernel: XKernel) -> u32 {
-        let mut counter = 0;
-        for y in 0..self.height {
-            for x in 0..self.width {
-                if x > 0 && x < self.width - 1 && y > 0 && y < self.height -1 {
-                    if kernel.search(&self.board, x, y) {
-                        counter += 1;
-                    }
-                }
-            }
-        }
-        
-        counter
-    }
+struct XKernel;
 
-    fn accept(&self, kernel: XmasKernel) -> u32 {
-        let mut counter = 0;
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let enable_row_check = x < self.width - kernel.size + 1;
-                let enable_column_check = y < self.height - kernel.size + 1;
-                if enable_row_check {
-                    if kernel.search_row(&self.board, x, y) {
-                        counter += 1;
-                    }
-                }
-                if enable_column_check {
-                    if kernel.search_column(&self.board, x, y) {
-                        counter += 1;
-                    }
-                }
-                if enable_row_check && enable_column_check {
-                    if kernel.search_diagonal_right(&self.board, x, y) {
-                        counter += 1;
-                    }
-                    if kernel.search_diagonal_left(&self.board, x + kernel.size - 1, y) {
-                        counter += 1;
-                    }
-                }
-            }
+impl XKernel {
+    fn search(&self, board: &Grid<char>, center: Point) -> bool {
+        if board.at(center) != Some(&'A') {
+            return false;
         }
 
-        counter
+        let is_mas = |offset: Point| matches!((board.at(center + offset), board.at(center - offset)), (Some('M'), Some('S')) | (Some('S'), Some('M')));
+        is_mas(Point::new(-1, -1)) && is_mas(Point::new(-1, 1))
     }
 }
 
 impl XmasKernel {
-    fn search_row(&self, board: &[Vec<&str>], x_offset: usize, y_offset: usize) -> bool {
-        let row = self.row_slice(board, x_offset, y_offset);
-        if self.check_slice(row) {
-            return true;
-        }
-        false
-    }
-
-    fn row_slice<'a>(&self, board: &'a [Vec<&'a str>], x_offset: usize, y_offset: usize) -> &'a [&'a str] {
-        &board[y_offset][x_offset..x_offset+self.size]
-    }
-
-    fn search_column(&self, board: &[Vec<&str>], x_offset: usize, y_offset: usize) -> bool {
-        let column = self.column_slice(board, x_offset, y_offset);
-        if self.check_slice(&column) {
-            return true;
-        }
-        false
-    }
-
-    fn column_slice<'a>(&self, board: &'a [Vec<&'a str>], x_offset: usize, y_offset: usize) -> Vec<&'a str> {
-        let mut slice = Vec::new();
-        for row in y_offset..y_offset + self.size {
-            slice.push(board[row][x_offset]);
-        }
-        slice
-    }
-
-    fn search_diagonal_right(&self, board: &[Vec<&str>], x_offset: usize, y_offset: usize) -> bool {
-        let diagonal = self.diagonal_slice_right(board, x_offset, y_offset);
-        if self.check_slice(&diagonal) {
-            return true;
+    fn new(target: &str) -> Self {
+        XmasKernel {
+            target: target.chars().collect()
         }
-        false
     }
 
-    fn diagonal_slice_right<'a>(&self, board: &'a [Vec<&'a str>], x_offset: usize, y_offset: usize) -> Vec<&'a str> {
-        let mut slice = Vec::new();
-        for i in 0..self.size {
-            slice.push(board[y_offset + i][x_offset + i]);
-        }
-        slice
+    /// Counts the occurrences of the target in a line of the board, forwards and backwards.
+    fn search<'a>(&self, line: impl Iterator<Item = &'a char>) -> u32 {
+        let line = line.copied().collect::<Vec<_>>();
+        line.windows(self.target.len())
+            .filter(|window| *window == self.target.as_slice() || window.iter().rev().eq(self.target.iter()))
+            .count() as u32
     }
+}
 
-    fn search_diagonal_left(&self, board: &[Vec<&str>], x_offset: usize, y_offset: usize) -> bool {
-        let diagonal = self.diagonal_slice_left(board, x_offset, y_offset);
-        self.check_slice(&diagonal)
-    }
+/// Counts all occurrences of `XMAS` horizontally, vertically, diagonally and backwards.
+pub fn word_search(board: &Grid<char>) -> u32 {
+    let kernel = XmasKernel::new("XMAS");
+    let rows = board.rows().map(|row| kernel.search(row.iter())).sum::<u32>();
+    let columns = board.columns().map(|column| kernel.search(column)).sum::<u32>();
+    let diagonals = board.diagonals().map(|diagonal| kernel.search(diagonal)).sum::<u32>();
+    let anti_diagonals = board.anti_diagonals().map(|diagonal| kernel.search(diagonal)).sum::<u32>();
 
-    fn diagonal_slice_left<'a>(&self, board: &'a [Vec<&'a str>], x_offset: usize, y_offset: usize) -> Vec<&'a str> {
-        let mut slice = Vec::new();
-        for i in 0..self.size {
-            slice.push(board[y_offset + i][x_offset - i]);
-        }
-        slice
-    }
+    rows + columns + diagonals + anti_diagonals
+}
 
-    fn check_slice(&self, slice: &[&str]) -> bool {
-        match slice {
-            ["X", "M", "A", "S"] => true,
-            ["S", "A", "M", "X"] => true,
-            _ => false
-        }
-    }
+/// Counts all positions where two diagonal `MAS` cross in the shape of an X.
+pub fn x_search(board: &Grid<char>) -> u32 {
+    board.positions().filter(|&position| XKernel.search(board, position.into())).count() as u32
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day4::{word_search, x_search};
+    use super::*;
 
-    #[test]
-    fn part1() {
-        let input = r#"MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX
-"#;
-        assert_eq!(18, word_search(input));
-    }
+    crate::examples::example_tests!(CeresSearch, 4);
 
     #[test]
-    fn part2() {
-        let input = r#"MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX
-"#;
-        assert_eq!(9, x_search(input));
+    fn small_boards() {
+        assert_eq!(0, word_search(&Grid::from_str("XM\nAS").unwrap()));
+        assert_eq!(1, word_search(&Grid::from_str("SAMX").unwrap()));
+        assert!(Grid::from_str("XMAS\nXMA").is_err());
     }
 }
\ No newline at end of file
@@ -1,80 +1,117 @@
+//! Day 5: Print Queue, validating page updates against ordering rules.
+
+use crate::error::AocError;
+use crate::parsing;
+use crate::random::Rng;
+use crate::solution::{Answer, Solution};
 use std::collections::HashMap;
 use std::str::FromStr;
 
-pub fn part1() {
-    let mut input = std::str::from_utf8(include_bytes!("../../res/day5/part1")).unwrap();
-    println!("{}", ordered_pages(&mut input));
-}
+/// Part 1 sums the middle pages of correct updates, part 2 those of the fixed incorrect updates.
+pub struct PrintQueue;
+
+impl Solution for PrintQueue {
+    type Input<'a> = (PageOrderings, PageUpdates);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        parse_print_queue(input)
+    }
+
+    fn part1((page_orderings, page_updates): &Self::Input<'_>) -> Answer {
+        ordered_pages(page_orderings, page_updates).into()
+    }
 
-pub fn part2() {
-    let mut input = std::str::from_utf8(include_bytes!("../../res/day5/part1")).unwrap();
-    println!("{}", reordered_pages(&mut input));
+    fn part2((page_orderings, page_updates): &Self::Input<'_>) -> Answer {
+        reordered_pages(page_orderings, page_updates).into()
+    }
+
+    /// Rules ordering every pair of up to 49 pages followed by `size` updates, about half of
+    /// them in the right order.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_print_queue(rng, size)
+    }
 }
 
-fn ordered_pages(input: &str) -> u32 {
-    let (ordering_rules, page_updates) = input.split_once("\n\n").unwrap();
-    let page_orderings = PageOrderings::from_str(ordering_rules).unwrap();
-    let page_updates = PageUpdates::from_str(page_updates).unwrap();
+/// Splits the input into the ordering rules and the page updates following the blank line.
+/// Updates that cannot be put in order because the rules for their pages contradict each other
+/// are reported as errors.
+pub fn parse_print_queue(input: &str) -> Result<(PageOrderings, PageUpdates), AocError> {
+    let (ordering_rules, updates) = input
+        .split_once("\n\n")
+        .ok_or_else(|| AocError::at_end(input, "expected a blank line between the ordering rules and the page updates"))?;
+    let page_orderings = PageOrderings::from_str(ordering_rules).map_err(|error| error.within(input, ordering_rules))?;
+    let page_updates = PageUpdates::from_str(updates).map_err(|error| error.within(input, updates))?;
+
+    for (line, page) in updates.lines().zip(page_updates.pages.iter()) {
+        if !page.validate_page(&page_orderings) && page.fixed(&page_orderings).is_none() {
+            return Err(AocError::at(input, line, "the ordering rules for this update contain a cycle"));
+        }
+    }
 
-    page_updates.filter_pages(&page_orderings)
+    Ok((page_orderings, page_updates))
 }
 
-fn reordered_pages(input: &str) -> u32 {
-    let (ordering_rules, page_updates) = input.split_once("\n\n").unwrap();
-    let page_orderings = PageOrderings::from_str(ordering_rules).unwrap();
-    let mut page_updates = PageUpdates::from_str(page_updates).unwrap();
-    
-    let mut broken_pages = page_updates.broken_pages(&page_orderings);
-    broken_pages.iter_mut().for_each(|page| page.fix_page(&page_orderings));
-    let fixed_pages = broken_pages;
-    fixed_pages.into_iter().map(|page| page.middle_item()).sum()
+/// Sums the middle page of every update that already satisfies all ordering rules.
+pub fn ordered_pages(page_orderings: &PageOrderings, page_updates: &PageUpdates) -> u32 {
+    page_updates.filter_pages(page_orderings)
 }
 
-struct PageOrderings {
+/// Fixes the order of every update violating the ordering rules and sums their middle pages.
+/// Updates whose rules contain a cycle have no fixed order and are skipped.
+pub fn reordered_pages(page_orderings: &PageOrderings, page_updates: &PageUpdates) -> u32 {
+    page_updates.broken_pages(page_orderings)
+        .filter_map(|page| page.fixed(page_orderings))
+        .map(|page| page.middle_item())
+        .sum()
+}
+
+/// Ordering rules, mapping each page to the pages that must be printed after it.
+pub struct PageOrderings {
     orderings: HashMap<u32, Vec<u32>>,
 }
 
 impl FromStr for PageOrderings {
-    type Err = String;
+    type Err = AocError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
         let mut orderings = HashMap::new();
-        input.lines()
-            .map(|line| line.split_once("|").unwrap())
-            .map(|(lhs, rhs)| (lhs.parse::<u32>().unwrap(), rhs.parse::<u32>().unwrap()))
-            .for_each(|(lhs, rhs)| {
-                orderings.entry(lhs).or_insert(Vec::new()).push(rhs);
-            });
+        for line in input.lines() {
+            let (lhs, rhs) = line.split_once("|").ok_or_else(|| AocError::at(input, line, "expected an ordering rule 'X|Y'"))?;
+            let (lhs, rhs) = (parsing::number::<u32>(input, lhs)?, parsing::number::<u32>(input, rhs)?);
+            orderings.entry(lhs).or_insert(Vec::new()).push(rhs);
+        }
 
         Ok(PageOrderings { orderings })
     }
 }
 
-struct PageUpdates {
+/// The list of page updates, one per line.
+#[derive(Clone)]
+pub struct PageUpdates {
     pages: Vec<Page>,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct Page {
     values: Vec<u32>
 }
 
 impl FromStr for PageUpdates {
-    type Err = String;
+    type Err = AocError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
         let pages = input.lines()
-            .map(|line| Page::from_str(line).unwrap())
-            .collect::<Vec<_>>();
+            .map(|line| Page::from_str(line).map_err(|error| error.within(input, line)))
+            .collect::<Result<Vec<_>, _>>()?;
         Ok(PageUpdates { pages })
     }
 }
 
 impl FromStr for Page {
-    type Err = String;
+    type Err = AocError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let values = input.split(',').map(|item| item.parse::<u32>().unwrap()).collect::<Vec<_>>();
+        let values = input.split(',').map(|item| parsing::number::<u32>(input, item)).collect::<Result<Vec<_>, _>>()?;
         Ok(Page { values })
     }
 }
@@ -84,8 +121,8 @@ impl PageUpdates {
         self.pages.iter().filter(|page| page.validate_page(orderings)).map(Page::middle_item).sum()
     }
     
-    fn broken_pages(&mut self, ordering: &PageOrderings) -> Vec<&mut Page> {
-        self.pages.iter_mut().filter(|page| !page.validate_page(ordering)).collect::<Vec<_>>()
+    fn broken_pages<'a>(&'a self, ordering: &'a PageOrderings) -> impl Iterator<Item = &'a Page> {
+        self.pages.iter().filter(|page| !page.validate_page(ordering))
     }
 }
 
@@ -99,27 +136,24 @@ impl Page {
         self.values[middle]
     }
     
-    fn fix_page(&mut self, orderings: &PageOrderings) {
-        let misplaced_value = self.values
-            .iter()
-            .enumerate()
-            .find_map(|(index, item)| if let Some(broken_position) = orderings.check_item(*item, index, &self.values) {
-                Some((broken_position, index))
-            } else {
-                None
-            });
-        
-        match misplaced_value {
-            Some((broken_pos, index)) => { 
-                self.values.swap(index, broken_pos);
-                self.fix_page(orderings);
-            },
-            None => ()
+    /// The pages in an order satisfying the rules, placing the first remaining page that no other
+    /// remaining page has to precede. `None` if the rules for these pages contain a cycle.
+    fn fixed(&self, orderings: &PageOrderings) -> Option<Page> {
+        let mut remaining = self.values.clone();
+        let mut values = Vec::with_capacity(remaining.len());
+        while !remaining.is_empty() {
+            let next = remaining.iter().position(|page| !remaining.iter().any(|other| orderings.precedes(*other, *page)))?;
+            values.push(remaining.remove(next));
         }
+        Some(Page { values })
     }
 }
 
 impl PageOrderings {
+    fn precedes(&self, before: u32, after: u32) -> bool {
+        self.orderings.get(&before).is_some_and(|constraints| constraints.contains(&after))
+    }
+
     fn check_item(&self, item: u32, index: usize, values: &[u32]) -> Option<usize> {
         let values = &values[0..index];
         match self.orderings.get(&item) {
@@ -133,73 +167,60 @@ impl PageOrderings {
     }
 }
 
+/// Page numbers are ranked by a shuffled order, so the rules never contradict each other.
+fn generate_print_queue(rng: &mut Rng, size: usize) -> String {
+    let mut pages = (10..=99).collect::<Vec<u32>>();
+    rng.shuffle(&mut pages);
+    pages.truncate(size.clamp(5, 49));
+
+    let mut rules = Vec::new();
+    for (index, before) in pages.iter().enumerate() {
+        rules.extend(pages[index + 1..].iter().map(|after| format!("{}|{}", before, after)));
+    }
+    rng.shuffle(&mut rules);
+
+    let updates = (0..size).map(|_| {
+        let mut update = pages.clone();
+        rng.shuffle(&mut update);
+        update.truncate(rng.range(2..=(pages.len() as u64 - 1) / 2) as usize * 2 + 1);
+        if rng.chance(0.5) {
+            update.sort_by_key(|page| pages.iter().position(|other| other == page));
+        }
+        update.iter().map(u32::to_string).collect::<Vec<_>>().join(",")
+    });
+
+    format!("{}\n\n{}\n", rules.join("\n"), updates.collect::<Vec<_>>().join("\n"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    crate::examples::example_tests!(PrintQueue, 5);
+
     #[test]
-    fn part1() {
-        let input = r#"47|53
-97|13
-97|61
-97|47
-75|29
-61|13
-75|53
-29|13
-97|29
-53|29
-61|53
-97|53
-61|29
-47|13
-75|47
-97|75
-47|61
-75|61
-47|29
-75|13
-53|13
-
-75,47,61,53,29
-97,61,53,29,13
-75,29,13
-75,97,47,61,53
-61,13,29
-97,13,75,29,47"#;
-        assert_eq!(143, ordered_pages(input));
+    fn report_malformed_input() {
+        assert_eq!(
+            Some(AocError::parse(2, 3, "invalid number 'x'")),
+            parse_print_queue("1|2\n3|x\n\n1,2,3").err()
+        );
+        assert_eq!(
+            Some(AocError::parse(5, 5, "invalid number ''")),
+            parse_print_queue("1|2\n3|4\n\n1,2,3\n4,5,").err()
+        );
+        assert_eq!(
+            Some(AocError::parse(1, 1, "expected an ordering rule 'X|Y'")),
+            parse_print_queue("12\n\n1,2").err()
+        );
+        assert!(parse_print_queue("1|2\n3|4").is_err());
     }
 
     #[test]
-    fn part2() {
-        let input = r#"47|53
-97|13
-97|61
-97|47
-75|29
-61|13
-75|53
-29|13
-97|29
-53|29
-61|53
-97|53
-61|29
-47|13
-75|47
-97|75
-47|61
-75|61
-47|29
-75|13
-53|13
-
-75,47,61,53,29
-97,61,53,29,13
-75,29,13
-75,97,47,61,53
-61,13,29
-97,13,75,29,47"#;
-        assert_eq!(123, reordered_pages(input));
+    fn report_contradicting_rules() {
+        assert_eq!(
+            Some(AocError::parse(6, 1, "the ordering rules for this update contain a cycle")),
+            parse_print_queue("1|2\n2|3\n3|1\n\n4,5\n3,2,1\n").err()
+        );
+        assert!(parse_print_queue("1|2\n2|1\n\n1,3\n").is_ok());
     }
 }
\ No newline at end of file
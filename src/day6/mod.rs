@@ -1,23 +1,44 @@
+//! Day 6: Guard Gallivant, predicting the patrol route of a guard.
+
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::point::Point;
+use crate::random::Rng;
+use crate::solution::{Answer, Solution};
 use std::collections::HashSet;
 use std::str::FromStr;
 
-pub fn part1() {
-    let input = include_str!("../../res/day6/part1");
-    println!("{}", guard_path(input));
-}
+/// Part 1 counts the tiles the guard visits, part 2 the obstructions that trap the guard in a loop.
+pub struct GuardGallivant;
+
+impl Solution for GuardGallivant {
+    type Input<'a> = Board;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        Board::from_str(input)
+    }
+
+    fn part1(board: &Self::Input<'_>) -> Answer {
+        guard_path(board.clone()).into()
+    }
+
+    fn part2(board: &Self::Input<'_>) -> Answer {
+        obstruction_loops(board.clone()).into()
+    }
 
-pub fn part2() {
-    let input = include_str!("../../res/day6/part1");
-    println!("{}", obstruction_loops(input));
+    /// A `size` by `size` lab with scattered obstructions, which the guard can leave.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        generate_lab(rng, size)
+    }
 }
 
-fn guard_path(input: &str) -> u32 {
-    let mut board = Board::from_str(input).unwrap();
+/// Counts the distinct positions the guard visits before leaving the board.
+pub fn guard_path(mut board: Board) -> u32 {
     board.predict_guard_movement().len() as u32
 }
 
-fn obstruction_loops(input: &str) -> u32 {
-    let mut board = Board::from_str(input).unwrap();
+/// Counts the positions on which a single new obstruction makes the guard walk in a loop.
+pub fn obstruction_loops(mut board: Board) -> u32 {
     let seen_positions = board.predict_guard_movement();
     let initial_guard_position = board.find_guard();
     board.find_loops(&seen_positions, initial_guard_position)
@@ -27,67 +48,45 @@ fn obstruction_loops(input: &str) -> u32 {
 enum Tile {
     Free,
     Occupied,
-    Guard(Direction)
-}
-
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
-enum Direction {
-    Left,
-    Right,
-    Up,
-    Down
-}
-
-impl Direction {
-    fn turn_right(&mut self) {
-        match self {
-            Direction::Left => *self = Direction::Up,
-            Direction::Right => *self = Direction::Down,
-            Direction::Up => *self = Direction::Right,
-            Direction::Down => *self = Direction::Left,
-        }
-    }
-    
-    fn move_position(&self, (x, y): (usize, usize)) -> (usize, usize) {
-        match self {
-            Direction::Up => (x, y - 1),
-            Direction::Down => (x, y + 1),
-            Direction::Left => (x - 1, y),
-            Direction::Right => (x + 1, y),
-        }
-    }
+    Guard
 }
 
+/// The lab map with its obstructions and the starting position of the guard.
 #[derive(Clone, Debug)]
-struct Board {
-    tiles: Vec<Vec<Tile>>,
-    width: usize,
-    height: usize
+pub struct Board {
+    tiles: Grid<Tile>
 }
 
 impl FromStr for Board {
-    type Err = String;
+    type Err = AocError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let tiles = input
-            .lines()
-            .map(|line| line.chars().map(|c| match c {
-                '.' => Tile::Free,
-                '#' => Tile::Occupied,
-                '^' => Tile::Guard(Direction::Up),
-                _ => unreachable!()
-            }).collect::<Vec<_>>()).collect::<Vec<_>>();
-        let width = tiles[0].len();
-        let height = tiles.len();
-
-        Ok(Board { tiles, width , height })
+        let tiles = Grid::parse(input, |c| match c {
+            '.' => Ok(Tile::Free),
+            '#' => Ok(Tile::Occupied),
+            '^' => Ok(Tile::Guard),
+            _ => Err(format!("unexpected character '{}'", c))
+        })?;
+
+        let (x, y) = match tiles.find_all(|tile| matches!(tile, Tile::Guard)).take(2).collect::<Vec<_>>()[..] {
+            [] => return Err(AocError::at_end(input, "no guard '^' found")),
+            [_, (x, y)] => return Err(AocError::parse(y + 1, x + 1, "more than one guard '^' found")),
+            [guard, ..] => guard,
+        };
+        let guard = Point::from((x, y));
+        if Point::ORTHOGONAL.iter().all(|direction| matches!(tiles.at(guard + *direction), Some(Tile::Occupied))) {
+            return Err(AocError::parse(y + 1, x + 1, "the guard is boxed in by obstructions"));
+        }
+
+        Ok(Board { tiles })
     }
 }
 
 impl Board {
-    fn predict_guard_movement(&mut self) -> HashSet<(usize, usize)> {
+    /// Walks the guard until it leaves the board and returns all visited positions.
+    pub fn predict_guard_movement(&mut self) -> HashSet<Point> {
         let mut guard_position = self.find_guard();
-        let mut direction = Direction::Up;
+        let mut direction = Point::UP;
         let mut seen_positions = HashSet::new();
 
         loop {
@@ -101,110 +100,116 @@ impl Board {
         seen_positions
     }
 
-    fn find_loops(&mut self, seen_positions: &HashSet<(usize, usize)>, initial_position: (usize, usize)) -> u32 {
+    /// Places an obstruction on each of the `seen_positions` in turn and counts how many of them
+    /// make the guard starting at `initial_position` walk in a loop.
+    pub fn find_loops(&mut self, seen_positions: &HashSet<Point>, initial_position: Point) -> u32 {
         let mut loops = 0;
-        for position in seen_positions {
-            if position == &initial_position {
+        for &position in seen_positions {
+            if position == initial_position {
                 continue
             }
             
-            self.tiles[position.1][position.0] = Tile::Occupied;
+            self.tiles[position] = Tile::Occupied;
 
-            let mut direction = Direction::Up;
-            let mut guard_position = initial_position.clone();
+            let mut direction = Point::UP;
+            let mut guard_position = initial_position;
             let mut seen_with_direction = HashSet::new();
             loop {
-                if !seen_with_direction.insert((guard_position, direction.clone())) {
+                if !seen_with_direction.insert((guard_position, direction)) {
                     loops += 1;
                     break
                 }
                 match self.move_guard(guard_position, &mut direction) {
                     Some(position) => guard_position = position,
-                    None => break
+                    None => {
+                        // Boxed in by the new obstruction, the guard turns on the spot forever.
+                        if self.tiles.contains(guard_position + direction) {
+                            loops += 1;
+                        }
+                        break
+                    }
                 }
             }
 
-            self.tiles[position.1][position.0] = Tile::Free;
+            self.tiles[position] = Tile::Free;
         }
         
         loops
     }
     
-    fn move_guard(&mut self, (x, y): (usize, usize), direction: &mut Direction) -> Option<(usize, usize)> {
-        if self.is_outside((x, y), direction) {
-            return None;
-        }
-        let new_guard_position = direction.move_position((x, y));
-        if self.is_occupied(new_guard_position) {
-            direction.turn_right();
-            return self.move_guard((x, y), direction);
-        }
-        Some(new_guard_position)
-    }
-
-    fn find_guard(&self) -> (usize, usize) {
-        for (row, columns) in self.tiles.iter().enumerate() {
-            for (column, tile) in columns.iter().enumerate() {
-                match tile {
-                    Tile::Guard(_) => return (column, row),
-                    _ => continue
-                }
+    /// The next position of the guard, turning right in front of obstructions, or `None` if the
+    /// guard leaves the board or is boxed in by obstructions on all four sides.
+    fn move_guard(&self, position: Point, direction: &mut Point) -> Option<Point> {
+        for _ in 0..4 {
+            let new_guard_position = position + *direction;
+            match self.tiles.at(new_guard_position)? {
+                Tile::Occupied => *direction = direction.rotate_right(),
+                _ => return Some(new_guard_position)
             }
         }
+        None
+    }
 
-        panic!("No guard found");
+    /// Returns the starting position of the guard, which parsing made sure exists.
+    pub fn find_guard(&self) -> Point {
+        self.tiles.find(|tile| matches!(tile, Tile::Guard)).expect("No guard found").into()
     }
+}
 
-    fn is_outside(&self, (x, y): (usize, usize), direction: &Direction) -> bool {
-        match direction {
-            Direction::Up => y == 0,
-            Direction::Down => y == self.height - 1,
-            Direction::Left => x == 0,
-            Direction::Right => x == self.width - 1
+/// Tries a few starting positions for the guard and keeps the one with the longest way out, so
+/// that the guard crosses a good part of the lab.
+fn generate_lab(rng: &mut Rng, size: usize) -> String {
+    loop {
+        let mut lab = Grid::new(size, size, (0..size * size).map(|_| if rng.chance(0.05) { '#' } else { '.' }).collect());
+        let free = lab.find_all(|cell| *cell == '.').collect::<Vec<_>>();
+        if free.is_empty() {
+            continue;
+        }
+        let guard = (0..10)
+            .map(|_| *rng.pick(&free))
+            .filter_map(|guard| Some((escape_length(&lab, guard.into())?, guard)))
+            .max()
+            .map(|(_, guard)| guard);
+        if let Some(guard) = guard {
+            lab[guard] = '^';
+            return lab.to_string();
         }
     }
+}
 
-    fn is_occupied(&self, (x, y): (usize, usize)) -> bool {
-        match self.tiles[y][x] {
-            Tile::Occupied => true,
-            _ => false
+/// The number of steps the guard takes to leave the lab, or `None` if the guard walks in a loop
+/// or is boxed in, which would make the puzzle unsolvable.
+fn escape_length(lab: &Grid<char>, mut position: Point) -> Option<usize> {
+    let mut direction = Point::UP;
+    let mut seen = HashSet::new();
+    while seen.insert((position, direction)) {
+        match lab.at(position + direction) {
+            None => return Some(seen.len()),
+            Some('#') => direction = direction.rotate_right(),
+            Some(_) => position += direction,
         }
     }
+    None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    crate::examples::example_tests!(GuardGallivant, 6);
+
     #[test]
-    fn part1() {
-        let input = r#"....#.....
-.........#
-..........
-..#.......
-.......#..
-..........
-.#..^.....
-........#.
-#.........
-......#..."#;
-
-        assert_eq!(41, guard_path(input));
+    fn report_malformed_board() {
+        assert_eq!(Some(AocError::parse(2, 3, "unexpected character 'x'")), Board::from_str("..#\n.^x\n").err());
+        assert_eq!(Some(AocError::parse(3, 1, "no guard '^' found")), Board::from_str("..#\n...\n").err());
+        assert_eq!(Some(AocError::parse(2, 2, "more than one guard '^' found")), Board::from_str("..^\n.^^\n").err());
+        assert_eq!(Some(AocError::parse(2, 2, "the guard is boxed in by obstructions")), Board::from_str(".#.\n#^#\n.#.\n").err());
     }
 
     #[test]
-    fn part2() {
-        let input = r#"....#.....
-.........#
-..........
-..#.......
-.......#..
-..........
-.#..^.....
-........#.
-#.........
-......#..."#;
-
-        assert_eq!(6, obstruction_loops(input));
+    fn stop_when_boxed_in() {
+        let board = Board::from_str(".#.\n#^.\n.#.\n").unwrap();
+        assert_eq!(2, guard_path(board.clone()));
+        assert_eq!(1, obstruction_loops(board));
     }
 }
\ No newline at end of file
@@ -1,14 +1,42 @@
-use std::ops::Add;
-use winnow::Parser;
+//! Day 7: Bridge Repair, restoring the operators of calibration equations.
 
-pub fn part1() {
-    let input = include_str!("../../res/day7/part1");
-    println!("{}", compute_calibrations(input, 2));
-}
+use crate::error::AocError;
+use crate::parsing;
+use crate::random::Rng;
+use crate::registry::Part;
+use crate::solution::{Answer, Solution};
+use std::io::BufRead;
+
+/// Part 1 allows `+` and `*` as operators, part 2 additionally allows concatenation.
+pub struct BridgeRepair;
+
+impl Solution for BridgeRepair {
+    type Input<'a> = Vec<(usize, Vec<usize>)>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        parse_equations(input)
+    }
+
+    fn part1(equations: &Self::Input<'_>) -> Answer {
+        sum_calibrations(equations, 2).into()
+    }
+
+    fn part2(equations: &Self::Input<'_>) -> Answer {
+        sum_calibrations(equations, 3).into()
+    }
+
+    fn stream(reader: &mut dyn BufRead, part: Part) -> Option<Result<Answer, AocError>> {
+        let num_operators = match part {
+            Part::One => 2,
+            Part::Two => 3,
+        };
+        Some(compute_calibrations_from(reader, num_operators).map(Answer::from))
+    }
 
-pub fn part2() {
-    let input = include_str!("../../res/day7/part1");
-    println!("{}", compute_calibrations(input, 3));
+    /// `size` equations of 2 to 12 operands, about half of which can be made true.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size).map(|_| generate_equation(rng) + "\n").collect()
+    }
 }
 
 #[derive(Debug)]
@@ -19,11 +47,15 @@ enum Operation {
 }
 
 impl Operation {
-    fn evaluate(&self, lhs: usize, rhs: usize) -> usize {
+    /// `None` if the result overflows, which no target can be.
+    fn evaluate(&self, lhs: usize, rhs: usize) -> Option<usize> {
         match self {
-            Operation::Add => lhs + rhs,
-            Operation::Mul => lhs * rhs,
-            Operation::Concat => lhs.to_string().add(&rhs.to_string()).parse::<usize>().unwrap()
+            Operation::Add => lhs.checked_add(rhs),
+            Operation::Mul => lhs.checked_mul(rhs),
+            Operation::Concat => {
+                let digits = rhs.checked_ilog10().unwrap_or(0) + 1;
+                lhs.checked_mul(10usize.checked_pow(digits)?)?.checked_add(rhs)
+            }
         }
     }
 }
@@ -39,73 +71,140 @@ impl From<usize> for Operation {
     }
 }
 
-fn compute_calibrations(input: &str, num_operators: usize) -> usize {
-    input.lines()
-        .map(|line| line.split_once(": ").unwrap())
-        .map(|(result, operands)| (result.parse::<usize>().unwrap(), operands.split_whitespace().map(|operand| operand.parse::<usize>().unwrap()).collect::<Vec<_>>()))
-        .map(|tuple| solve_equation(tuple, num_operators))
-        .sum()
+/// Parses one `result: operand operand ...` equation per line.
+pub fn parse_equations(input: &str) -> Result<Vec<(usize, Vec<usize>)>, AocError> {
+    let mut equations = Vec::new();
+    parsing::read_lines(input.as_bytes(), |line| {
+        equations.push(parse_equation(line)?);
+        Ok(())
+    })?;
+    Ok(equations)
 }
 
-fn solve_equation((expected_result, operands): (usize, Vec<usize>), num_operators: usize) -> usize {
-    let num_options = num_operators.pow(operands.len() as u32);
+/// Parses a single `result: operand operand ...` equation.
+pub fn parse_equation(line: &str) -> Result<(usize, Vec<usize>), AocError> {
+    let (result, operands) = line.split_once(": ").ok_or_else(|| AocError::at(line, line, "expected an equation 'result: operands'"))?;
+    let operands = operands.split_whitespace().map(|operand| parsing::number::<usize>(line, operand)).collect::<Result<Vec<_>, _>>()?;
+    if operands.is_empty() {
+        return Err(AocError::at_end(line, "expected at least one operand"));
+    }
+    Ok((parsing::number::<usize>(line, result)?, operands))
+}
 
-    for permutation in 0..num_options {
-        let mut equation_result = 0usize;
-        for (index, operand) in operands.iter().enumerate() {
-            if index == 0 {
-                equation_result = *operand;
-                continue;
-            }
-            let operation_code = permutation.checked_div(num_operators.pow((index - 1) as u32)).unwrap_or(0) % num_operators;
-            equation_result = Operation::from(operation_code).evaluate(equation_result, *operand);
-            if equation_result > expected_result {
-                break
-            }
-       }
+/// Sums the results of all equations that can be satisfied by combining their operands from left
+/// to right with the first `num_operators` operators out of `+`, `*` and concatenation.
+pub fn compute_calibrations(input: &str, num_operators: usize) -> Result<u64, AocError> {
+    compute_calibrations_from(input.as_bytes(), num_operators)
+}
 
-        if equation_result == expected_result {
-            return equation_result;
-        }
+/// [`compute_calibrations`] for equations read line by line from `reader`, holding one equation at
+/// a time.
+pub fn compute_calibrations_from(reader: impl BufRead, num_operators: usize) -> Result<u64, AocError> {
+    let mut calibrations = 0;
+    parsing::read_lines(reader, |line| {
+        calibrations += sum_calibrations(&[parse_equation(line)?], num_operators);
+        Ok(())
+    })?;
+    Ok(calibrations)
+}
+
+fn sum_calibrations(equations: &[(usize, Vec<usize>)], num_operators: usize) -> u64 {
+    equations.iter()
+        .map(|(expected_result, operands)| solve_equation(*expected_result, operands, num_operators) as u64)
+        .sum()
+}
+
+fn solve_equation(expected_result: usize, operands: &[usize], num_operators: usize) -> usize {
+    if can_reach(expected_result, operands[0], &operands[1..], num_operators) {
+        expected_result
+    } else {
+        0
     }
+}
 
-    0
+/// Tries the operators depth first instead of numbering every combination, which would overflow
+/// for long equations. Branches that overflow are dropped, and so are branches that already exceed
+/// the target unless a later multiplication by 0 could bring them back down.
+fn can_reach(expected_result: usize, result: usize, operands: &[usize], num_operators: usize) -> bool {
+    let Some((operand, rest)) = operands.split_first() else {
+        return result == expected_result;
+    };
+    let can_shrink = rest.contains(&0);
+    (0..num_operators).any(|operation_code| {
+        Operation::from(operation_code)
+            .evaluate(result, *operand)
+            .filter(|result| *result <= expected_result || can_shrink)
+            .is_some_and(|result| can_reach(expected_result, result, rest, num_operators))
+    })
+}
+
+/// Results stay below 10^14, far enough from overflowing while the operators are tried.
+fn generate_equation(rng: &mut Rng) -> String {
+    loop {
+        let largest = if rng.chance(0.5) { 9 } else { 999 };
+        let operands = (0..rng.range(2..=12)).map(|_| rng.range(1..=largest) as usize).collect::<Vec<_>>();
+        let result = operands[1..].iter().try_fold(operands[0], |result, &operand| match rng.range(0..=2) {
+            0 => result.checked_add(operand),
+            1 => result.checked_mul(operand),
+            _ => format!("{}{}", result, operand).parse().ok(),
+        });
+        let Some(result) = result.filter(|result| *result < 10usize.pow(14)) else { continue };
+        let result = if rng.chance(0.5) { result + rng.range(1..=9) as usize } else { result };
+        let operands = operands.iter().map(usize::to_string).collect::<Vec<_>>();
+        return format!("{}: {}", result, operands.join(" "));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    crate::examples::example_tests!(BridgeRepair, 7);
+
     #[test]
-    fn part1() {
-        let input = r#"190: 10 19
-3267: 81 40 27
-83: 17 5
-156: 15 6
-7290: 6 8 6 15
-161011: 16 10 13
-192: 17 8 14
-21037: 9 7 18 13
-292: 11 6 16 20"#;
-        assert_eq!(3749, compute_calibrations(input, 2));
+    fn concat() {
+        assert_eq!(Some(12), Operation::Concat.evaluate(1, 2));
+        assert_eq!(Some(10), Operation::Concat.evaluate(1, 0));
+        assert_eq!(Some(123456), Operation::Concat.evaluate(123, 456));
     }
 
     #[test]
-    fn part2() {
-        let input = r#"190: 10 19
-3267: 81 40 27
-83: 17 5
-156: 15 6
-7290: 6 8 6 15
-161011: 16 10 13
-192: 17 8 14
-21037: 9 7 18 13
-292: 11 6 16 20"#;
-        assert_eq!(11387, compute_calibrations(input, 3));
-    }
-    
+    fn treat_overflow_as_unreachable() {
+        assert_eq!(None, Operation::Mul.evaluate(usize::MAX / 2, 3));
+        assert_eq!(None, Operation::Concat.evaluate(usize::MAX / 10, 99));
+        assert_eq!(0, solve_equation(usize::MAX, &[usize::MAX, 2], 3));
+        assert_eq!(usize::MAX, solve_equation(usize::MAX, &[usize::MAX - 1, 1], 3));
+    }
+
     #[test]
-    fn concat() {
-        assert_eq!(12, Operation::Concat.evaluate(1, 2));
+    fn multiply_by_zero_after_overshooting() {
+        assert_eq!(3, solve_equation(3, &[2, 5, 0, 3], 2));
+        assert_eq!(8, solve_equation(8, &[7, 8, 0, 8], 3));
+        assert_eq!(0, solve_equation(4, &[7, 8, 0], 3));
+    }
+
+    #[test]
+    fn solve_long_equations() {
+        let operands = vec![2; 100];
+        assert_eq!(200, solve_equation(200, &operands, 2));
+        assert_eq!(0, solve_equation(199, &operands, 3));
+        let operands = vec![1; 10];
+        assert_eq!(1_111_111_111, solve_equation(1_111_111_111, &operands, 3));
+    }
+
+    #[test]
+    fn report_malformed_equations() {
+        assert_eq!(Some(AocError::parse(2, 1, "expected an equation 'result: operands'")), parse_equations("3: 1 2\n3 1 2").err());
+        assert_eq!(Some(AocError::parse(1, 6, "invalid number '2a'")), parse_equations("3: 1 2a").err());
+        assert_eq!(Some(AocError::parse(1, 4, "expected at least one operand")), parse_equations("3: ").err());
+    }
+
+    #[test]
+    fn stream_equations() {
+        let input = "190: 10 19\n3267: 81 40 27\n83: 17 5\n156: 15 6\n";
+        assert_eq!(Ok(3457), compute_calibrations(input, 2));
+        assert_eq!(Ok(3457), compute_calibrations_from(input.as_bytes(), 2));
+        assert_eq!(Ok(3613), compute_calibrations_from(input.as_bytes(), 3));
+        assert_eq!(Err(AocError::parse(2, 4, "invalid number '1x'")), compute_calibrations_from("3: 1 2\n3: 1x\n".as_bytes(), 3));
     }
 }
\ No newline at end of file
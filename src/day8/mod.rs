@@ -1,59 +1,86 @@
+//! Day 8: Resonant Collinearity, locating the antinodes of antennas.
+
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::point::Point;
+use crate::random::Rng;
+use crate::solution::{Answer, Solution};
 use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
 
-pub fn part1() {
-    let input = include_str!("../../res/day8/part1");
-    println!("{}", anti_node_count(input, Distance::ONE));
-}
+/// Part 1 counts antinodes at a single distance, part 2 antinodes at any multiple of it.
+pub struct ResonantCollinearity;
+
+impl Solution for ResonantCollinearity {
+    type Input<'a> = Antennas;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        Antennas::parse(input)
+    }
+
+    fn part1(antennas: &Self::Input<'_>) -> Answer {
+        anti_node_count(antennas, Distance::One).into()
+    }
 
-pub fn part2() {
-    let input = include_str!("../../res/day8/part1");
-    println!("{}", anti_node_count(input, Distance::UNLIMITED));
+    fn part2(antennas: &Self::Input<'_>) -> Answer {
+        anti_node_count(antennas, Distance::Unlimited).into()
+    }
+
+    /// A `size` by `size` map with a few antennas of up to 10 frequencies.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        const FREQUENCIES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+        let frequencies = (0..rng.range(1..=10)).map(|_| *rng.pick(FREQUENCIES) as char).collect::<Vec<_>>();
+        let cells = (0..size * size).map(|_| if rng.chance(0.04) { *rng.pick(&frequencies) } else { '.' }).collect();
+        Grid::new(size, size, cells).to_string()
+    }
 }
 
-#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
-struct Position {
-    x: i64,
-    y: i64,
+/// The antenna map, grouping antenna positions by their frequency.
+pub struct Antennas {
+    frequencies: HashMap<char, Vec<Point>>,
+    map: Grid<char>,
 }
 
+/// How far antinodes reach beyond a pair of antennas.
 #[derive(Copy, Clone)]
-enum Distance {
-    ONE,
-    UNLIMITED
+pub enum Distance {
+    One,
+    Unlimited
 }
 
-fn anti_node_count(input: &str, distance: Distance) -> u32 {
-    let height = input.lines().count();
-    let width = input.lines().next().unwrap().len();
-    let anti_node_positions = input
-        .lines()
-        .enumerate()
-        .fold(HashMap::new(), |mut map, (row, line)| {
-            line
-                .chars()
-                .enumerate()
-                .for_each(|(col, c)| if c != '.' {
-                    map
-                        .entry(c)
-                        .or_insert(Vec::new())
-                        .push(Position { x: col as i64, y: row as i64})
-                });
-            map
-        })
-        .into_iter()
-        .map(|(_, positions)| anti_nodes_for_frequency(positions, width, height, distance))
-        .reduce(|mut lhs, rhs| {
+impl Antennas {
+    /// Parses the map, treating every character except `.` as an antenna frequency.
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        let map = Grid::from_str(input)?;
+        let frequencies = map
+            .iter()
+            .filter(|(_, &c)| c != '.')
+            .fold(HashMap::new(), |mut frequencies, ((x, y), &c)| {
+                frequencies
+                    .entry(c)
+                    .or_insert(Vec::new())
+                    .push(Point::from((x, y)));
+                frequencies
+            });
+
+        Ok(Antennas { frequencies, map })
+    }
+}
+
+/// Counts the distinct positions within the map that contain an antinode.
+pub fn anti_node_count(antennas: &Antennas, distance: Distance) -> u32 {
+    let anti_node_positions = antennas.frequencies
+        .values()
+        .map(|positions| anti_nodes_for_frequency(positions, &antennas.map, distance))
+        .fold(HashSet::new(), |mut lhs, rhs| {
             lhs.extend(rhs);
             lhs
-        })
-        .unwrap();
+        });
 
-    anti_node_positions.iter().for_each(|p| println!("{:?}", p));
-    
     anti_node_positions.len() as u32
 }
 
-fn anti_nodes_for_frequency(positions: Vec<Position>, width: usize, height: usize, distance: Distance) -> HashSet<Position> {
+fn anti_nodes_for_frequency(positions: &[Point], map: &Grid<char>, distance: Distance) -> HashSet<Point> {
     let mut anti_nodes = HashSet::new();
     for (start, &pos1) in positions.iter().enumerate() {
         for &pos2 in positions[start..].iter() {
@@ -62,8 +89,8 @@ fn anti_nodes_for_frequency(positions: Vec<Position>, width: usize, height: usiz
             }
 
             match distance {
-                Distance::ONE => (),
-                Distance::UNLIMITED => {
+                Distance::One => (),
+                Distance::Unlimited => {
                     anti_nodes.insert(pos1);
                     anti_nodes.insert(pos2);
                 },
@@ -74,25 +101,25 @@ fn anti_nodes_for_frequency(positions: Vec<Position>, width: usize, height: usiz
             let mut a2 = pos2 + diff;
             loop {
                 let mut a1_out_of_bounds = false;
-                if check_bounds(a1, width, height) {
+                if map.contains(a1) {
                     anti_nodes.insert(a1);
                 } else {
                     a1_out_of_bounds = true;
                 }
 
                 let mut a2_out_of_bounds = false;
-                if check_bounds(a2, width, height) {
+                if map.contains(a2) {
                     anti_nodes.insert(a2);
                 } else {
                     a2_out_of_bounds = true;
                 }
 
                 match distance {
-                    Distance::ONE => break,
-                    Distance::UNLIMITED if a1_out_of_bounds && a2_out_of_bounds => break,
-                    Distance::UNLIMITED => {
-                        a1 = a1 - diff;
-                        a2 = a2 + diff;
+                    Distance::One => break,
+                    Distance::Unlimited if a1_out_of_bounds && a2_out_of_bounds => break,
+                    Distance::Unlimited => {
+                        a1 -= diff;
+                        a2 += diff;
                     }
                 }
             }
@@ -102,72 +129,15 @@ fn anti_nodes_for_frequency(positions: Vec<Position>, width: usize, height: usiz
     anti_nodes
 }
 
-fn check_bounds(pos: Position, width: usize, height: usize) -> bool {
-    if pos.x >= 0 && (pos.x as usize) < width && pos.y >= 0 && (pos.y as usize) < height {
-        return true;
-    }
-    false
-}
-
-impl std::ops::Add for Position {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Position {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
-    }
-}
-
-impl std::ops::Sub for Position {
-    type Output = Self;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        Position {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn part1() {
-        let input = r#"............
-........0...
-.....0......
-.......0....
-....0.......
-......A.....
-............
-............
-........A...
-.........A..
-............
-............"#;
-
-        assert_eq!(14, anti_node_count(&input, Distance::ONE));
-    }
+    crate::examples::example_tests!(ResonantCollinearity, 8);
 
     #[test]
-    fn part2() {
-        let input = r#"............
-........0...
-.....0......
-.......0....
-....0.......
-......A.....
-............
-............
-........A...
-.........A..
-............
-............"#;
-
-        assert_eq!(34, anti_node_count(&input, Distance::UNLIMITED));
+    fn without_antennas() {
+        assert_eq!(0, anti_node_count(&Antennas::parse("...\n...").unwrap(), Distance::Unlimited));
+        assert!(Antennas::parse("...\n..").is_err());
     }
 }
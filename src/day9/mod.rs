@@ -1,45 +1,79 @@
-use std::ops::Range;
+//! Day 9: Disk Fragmenter, compacting files on a disk.
 
-pub fn part1() {
-    let input = include_str!("../../res/day9/part1");
-    println!("{}", fragment_disk(input));
-}
+use crate::error::AocError;
+use crate::random::Rng;
+use crate::solution::{Answer, Solution};
 
-pub fn part2() {
-    let input = include_str!("../../res/day9/part1");
-    println!("{}", move_files(input));
-}
+/// Part 1 moves single blocks to compact the disk, part 2 moves whole files.
+pub struct DiskFragmenter;
 
-struct Cursor<'a> {
-    index: usize,
-    inner: Range<u32>,
-    label: u32,
-    increment: i64,
-    disk_map: &'a [u32]
-}
+impl Solution for DiskFragmenter {
+    type Input<'a> = Vec<u32>;
 
-fn fragment_disk(input: &str) -> u64 {
-    let input = input.chars().map(|c| c.to_digit(10).unwrap()).collect::<Vec<u32>>();
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        parse_disk_map(input)
+    }
 
-    let mut forward_cursor = Cursor::forward(&input);
-    let mut backward_cursor = Cursor::backward(&input);
+    fn part1(disk_map: &Self::Input<'_>) -> Answer {
+        fragment_disk(disk_map).into()
+    }
 
-    let mut checksum = 0u64;
-    let mut global_position = 0;
-    while forward_cursor.index() != backward_cursor.index() {
-        while let Some(label) = forward_cursor.next() {
-            checksum += (label * global_position) as u64;
-            global_position += 1;
-        }
-        (0..input[forward_cursor.index() - 1]).map(|_| backward_cursor.next_safe()).for_each(|label| {
-            checksum += (label * global_position) as u64;
-            global_position += 1;
-        })
+    fn part2(disk_map: &Self::Input<'_>) -> Answer {
+        move_files(disk_map).into()
     }
 
-    while let Some(label) = backward_cursor.next() {
-        checksum += (label * global_position) as u64;
-        global_position += 1;
+    /// A disk map of about `size` digits, always ending with a file.
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let digits = (0..size / 2 * 2 + 1).map(|index| rng.range(if index % 2 == 0 { 1..=9 } else { 0..=9 }).to_string());
+        digits.collect::<String>() + "\n"
+    }
+}
+
+/// Parses the dense disk map of alternating file and free space lengths.
+pub fn parse_disk_map(input: &str) -> Result<Vec<u32>, AocError> {
+    let disk_map = input.trim_end();
+    if disk_map.is_empty() {
+        return Err(AocError::parse(1, 1, "empty disk map"));
+    }
+    disk_map
+        .char_indices()
+        .map(|(index, c)| c.to_digit(10).ok_or_else(|| AocError::at(input, &disk_map[index..], format!("expected a digit, found {:?}", c))))
+        .collect()
+}
+
+/// Computes the checksum after moving blocks from the end of the disk into the leftmost free space.
+///
+/// Walks the disk map from the front while taking blocks from the last unmoved file, so the disk
+/// never needs to be expanded into blocks.
+pub fn fragment_disk(disk_map: &[u32]) -> u64 {
+    let mut remaining = disk_map.iter().map(|&length| length as u64).collect::<Vec<_>>();
+    let mut checksum = 0;
+    let mut position = 0;
+    let mut place = |label: usize, count: u64| {
+        // The labels of `count` blocks starting at `position`, summed in closed form.
+        checksum += label as u64 * (count * position + count * count.saturating_sub(1) / 2);
+        position += count;
+    };
+
+    let Some(last) = disk_map.len().checked_sub(1) else { return 0 };
+    let mut front = 0;
+    let mut back = last / 2 * 2;
+    while front <= back {
+        if front % 2 == 0 {
+            place(front / 2, remaining[front]);
+            remaining[front] = 0;
+        } else {
+            while remaining[front] > 0 && back > front {
+                let count = remaining[front].min(remaining[back]);
+                place(back / 2, count);
+                remaining[front] -= count;
+                remaining[back] -= count;
+                if remaining[back] == 0 {
+                    back -= 2;
+                }
+            }
+        }
+        front += 1;
     }
 
     checksum
@@ -51,15 +85,15 @@ enum State {
     Free(usize)
 }
 
-fn move_files(input: &str) -> usize {
-    let mut input = input
-        .chars()
-        .map(|c| c.to_digit(10).unwrap())
+/// Computes the checksum after moving each file, highest ID first, into the leftmost free span fitting it.
+pub fn move_files(disk_map: &[u32]) -> usize {
+    let mut input = disk_map
+        .iter()
         .enumerate()
         .map(|(i, len)| if i % 2 == 0 {
-            State::File(len as usize, (i / 2) as u32)
+            State::File(*len as usize, (i / 2) as u32)
         } else {
-            State::Free(len as usize)
+            State::Free(*len as usize)
         })
         .collect::<Vec<_>>();
 
@@ -67,19 +101,13 @@ fn move_files(input: &str) -> usize {
         let mut changed = false;
         for index in (0..input.len()).rev() {
             let free_slot_with_file = match input[index] {
-                State::File(length, label) => {
-                    let mut result = None;
-                    for i in 0..input.len() {
-                        match input[i] {
-                            State::Free(len) if len >= length && i < index => {
-                                result = Some((i, length, label, len - length));
-                                break;
-                            },
-                            _ => ()
-                        };
-                    }
-                    result
-                }
+                State::File(length, label) => input[..index]
+                    .iter()
+                    .enumerate()
+                    .find_map(|(i, state)| match state {
+                        State::Free(len) if *len >= length => Some((i, length, label, len - length)),
+                        _ => None
+                    }),
                 State::Free(_) => None
             };
 
@@ -121,66 +149,87 @@ fn move_files(input: &str) -> usize {
     sum
 }
 
-impl<'a> Cursor<'a> {
-    fn forward(disk_map: &'a [u32]) -> Self {
-        let inner = 0..disk_map[0];
-        let label = 0;
-        Cursor { index: 0 , inner, label, increment: 1, disk_map }
-    }
-
-    fn backward(disk_map: &'a [u32]) -> Self {
-        let last_file_index = match disk_map.len() {
-            len if len % 2 == 0 => len - 2,
-            len if len % 2 == 1 => len - 1,
-            _ => unreachable!(),
-        };
-        let inner = 0..disk_map[last_file_index];
-        let label = (disk_map.len() / 2) as u32;
-        Cursor { index: last_file_index, inner, label, increment: -1, disk_map }
-    }
-
-    fn next(&mut self) -> Option<u32> {
-        match self.inner.next() {
-            Some(_) => Some(self.label),
-            None => {
-                self.inner = 0..self.disk_map[self.advance()];
-                self.label = (self.label as i64 + self.increment) as u32;
-                None
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::{self, Rng};
+
+    crate::examples::example_tests!(DiskFragmenter, 9);
+
+    #[test]
+    fn report_malformed_disk_map() {
+        assert_eq!(Ok(vec![1, 2, 3]), parse_disk_map("123\n"));
+        assert_eq!(Err(AocError::parse(1, 3, "expected a digit, found 'x'")), parse_disk_map("12x3"));
+        assert_eq!(Err(AocError::parse(1, 4, "expected a digit, found '\\n'")), parse_disk_map("123\n4"));
+        assert!(parse_disk_map("").is_err());
+    }
+
+    /// The disk block by block, with the file ID of each block or `None` for free space.
+    fn blocks(disk_map: &[u32]) -> Vec<Option<usize>> {
+        disk_map
+            .iter()
+            .enumerate()
+            .flat_map(|(index, &length)| std::iter::repeat_n(if index % 2 == 0 { Some(index / 2) } else { None }, length as usize))
+            .collect()
+    }
+
+    fn checksum(blocks: &[Option<usize>]) -> usize {
+        blocks.iter().enumerate().map(|(position, label)| position * label.unwrap_or(0)).sum()
+    }
+
+    fn fragment_disk_naive(disk_map: &[u32]) -> usize {
+        let mut blocks = blocks(disk_map);
+        loop {
+            while blocks.last() == Some(&None) {
+                blocks.pop();
             }
+            let Some(free) = blocks.iter().position(Option::is_none) else { break };
+            blocks[free] = blocks.pop().unwrap();
         }
+        checksum(&blocks)
     }
 
-    fn next_safe(&mut self) -> u32 {
-        match self.next() {
-            Some(label) => label,
-            None => self.next().unwrap()
+    fn move_files_naive(disk_map: &[u32]) -> usize {
+        let mut blocks = blocks(disk_map);
+        for label in (0..disk_map.len().div_ceil(2)).rev() {
+            let start = blocks.iter().position(|block| *block == Some(label)).unwrap();
+            let length = disk_map[label * 2] as usize;
+            let free = (0..start).find(|&free| blocks[free..free + length].iter().all(Option::is_none));
+            if let Some(free) = free {
+                blocks[free..free + length].fill(Some(label));
+                blocks[start..start + length].fill(None);
+            }
         }
+        checksum(&blocks)
     }
 
-    fn index(&self) -> usize {
-        self.index
+    /// Files take 1 to 9 blocks and the free spans between them 0 to 9, as in the puzzle inputs.
+    fn random_disk_map(rng: &mut Rng) -> Vec<u32> {
+        (0..rng.range(1..=40)).map(|index| rng.range(if index % 2 == 0 { 1..=9 } else { 0..=9 }) as u32).collect()
     }
 
-    fn advance(&mut self) -> usize {
-        self.index = (self.index as i64 + self.increment * 2) as usize;
-        self.index
+    #[test]
+    fn fragment_disk_like_moving_single_blocks() {
+        random::check(2000, |rng| {
+            let disk_map = random_disk_map(rng);
+            assert_eq!(fragment_disk_naive(&disk_map) as u64, fragment_disk(&disk_map), "disk map {:?}", disk_map);
+        });
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
     #[test]
-    fn part1() {
-        let input = "2333133121414131402";
-        assert_eq!(1928, fragment_disk(input));
+    fn fragment_maps_ending_in_free_space() {
+        assert_eq!(0, fragment_disk(&[]));
+        assert_eq!(0, fragment_disk(&[6, 7]));
+        assert_eq!(6, fragment_disk(&[1, 2, 3, 4]));
+        assert_eq!(fragment_disk_naive(&[2, 3, 1, 4]) as u64, fragment_disk(&[2, 3, 1, 4]));
     }
 
     #[test]
-    fn part2() {
-        let input = "2333133121414131402";
-        assert_eq!(2858, move_files(input));
+    fn move_files_like_moving_whole_files() {
+        random::check(2000, |rng| {
+            let disk_map = random_disk_map(rng);
+            assert_eq!(move_files_naive(&disk_map), move_files(&disk_map), "disk map {:?}", disk_map);
+        });
     }
 }
 
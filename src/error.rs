@@ -0,0 +1,109 @@
+use std::fmt::{Display, Formatter};
+use winnow::stream::Offset;
+
+/// Errors raised while loading or parsing a puzzle input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AocError {
+    /// The puzzle input could not be read.
+    Input(String),
+    /// A request to the puzzle server failed or was rejected.
+    Http(String),
+    /// The puzzle input does not have the expected format. Lines and columns start at 1.
+    Parse { line: usize, column: usize, message: String },
+}
+
+impl AocError {
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> Self {
+        AocError::Parse { line, column, message: message.into() }
+    }
+
+    /// Reports an error at the start of `fragment`, which has to be a slice of `input`.
+    pub fn at(input: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let (line, column) = location(input, fragment);
+        AocError::parse(line, column, message)
+    }
+
+    /// Moves an error reported relative to `fragment` to its location in the enclosing `input`.
+    pub fn within(self, input: &str, fragment: &str) -> Self {
+        match self {
+            AocError::Parse { line, column, message } => {
+                let (fragment_line, fragment_column) = location(input, fragment);
+                let column = if line == 1 { fragment_column + column - 1 } else { column };
+                AocError::Parse { line: fragment_line + line - 1, column, message }
+            }
+            error => error,
+        }
+    }
+
+    /// Moves an error reported for a single line to line `line` of the enclosing input.
+    pub fn on_line(self, line: usize) -> Self {
+        match self {
+            AocError::Parse { line: offset, column, message } => AocError::Parse { line: line + offset - 1, column, message },
+            error => error,
+        }
+    }
+
+    /// Reports an error at the end of `input`, for inputs that stop before they are complete.
+    pub fn at_end(input: &str, message: impl Into<String>) -> Self {
+        AocError::at(input, &input[input.len()..], message)
+    }
+
+    /// The line of `input` a parse error points at, with a caret under its column.
+    pub fn excerpt(&self, input: &str) -> Option<String> {
+        let AocError::Parse { line, column, .. } = self else { return None };
+        let text = input.split('\n').nth(line.checked_sub(1)?)?.trim_end_matches('\r');
+        let number = line.to_string();
+        Some(format!("{} | {}\n{} | {}^", number, text, " ".repeat(number.len()), " ".repeat(column.saturating_sub(1))))
+    }
+}
+
+fn location(input: &str, fragment: &str) -> (usize, usize) {
+    let preceding = &input[..fragment.offset_from(&input)];
+    let line = preceding.matches('\n').count() + 1;
+    let line_start = preceding.rfind('\n').map(|index| index + 1).unwrap_or(0);
+    let column = preceding[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::Input(message) | AocError::Http(message) => write!(f, "{}", message),
+            AocError::Parse { line, column, message } => write!(f, "line {}, column {}: {}", line, column, message),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locate_fragment() {
+        let input = "12 34\n56 x8\n";
+        let fragment = &input[9..];
+        assert_eq!(AocError::parse(2, 4, "invalid"), AocError::at(input, fragment, "invalid"));
+        assert_eq!(AocError::parse(3, 1, "missing"), AocError::at_end(input, "missing"));
+    }
+
+    #[test]
+    fn relocate_into_enclosing_input() {
+        let input = "a|b\n\n1,2\n3,x";
+        let updates = &input[5..];
+        assert_eq!(AocError::parse(4, 3, "invalid"), AocError::parse(2, 3, "invalid").within(input, updates));
+        assert_eq!(AocError::parse(3, 2, "invalid"), AocError::parse(1, 2, "invalid").within(input, updates));
+        assert_eq!("line 3, column 2: invalid", AocError::parse(3, 2, "invalid").to_string());
+        assert_eq!(AocError::parse(7, 2, "invalid"), AocError::parse(1, 2, "invalid").on_line(7));
+    }
+
+    #[test]
+    fn point_at_column() {
+        let input = "a|b\n\n1,2\n3,x\n";
+        assert_eq!(Some("4 | 3,x\n  |   ^".to_string()), AocError::parse(4, 3, "invalid").excerpt(input));
+        assert_eq!(Some("5 | \n  | ^".to_string()), AocError::at_end(input, "missing").excerpt(input));
+        assert_eq!(None, AocError::parse(7, 1, "invalid").excerpt(input));
+        assert_eq!(None, AocError::Input("unreadable".to_string()).excerpt(input));
+    }
+}
@@ -0,0 +1,110 @@
+use crate::error::AocError;
+use crate::verify::Answers;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// A worked example from a puzzle, stored as `<name>.txt` with its answers in `<name>.answers`
+/// using the format of [`Answers`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub answers: Answers,
+}
+
+/// The examples directory of a day inside an inputs directory.
+pub fn examples_dir(directory: &Path, day: u8) -> PathBuf {
+    directory.join(format!("day{}", day)).join("examples")
+}
+
+/// Reads all examples of a day sorted by name, which are empty if the day has none.
+pub fn read(directory: &Path, day: u8) -> Result<Vec<Example>, AocError> {
+    let examples_dir = examples_dir(directory, day);
+    if !examples_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let read_error = |path: &Path, error: std::io::Error| AocError::Input(format!("Could not read {}: {}", path.display(), error));
+    let mut examples = std::fs::read_dir(&examples_dir)
+        .map_err(|error| read_error(&examples_dir, error))?
+        .map(|entry| entry.map(|entry| entry.path()).map_err(|error| read_error(&examples_dir, error)))
+        .filter(|path| path.as_ref().map_or(true, |path| path.extension().is_some_and(|extension| extension == "txt")))
+        .map(|path| {
+            let path = path?;
+            let input = std::fs::read_to_string(&path).map_err(|error| read_error(&path, error))?;
+            let answers_path = path.with_extension("answers");
+            let answers = std::fs::read_to_string(&answers_path).map_err(|error| read_error(&answers_path, error))?;
+            let answers = Answers::from_str(&answers).map_err(|error| AocError::Input(format!("{}: {}", answers_path.display(), error)))?;
+            let name = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+            Ok(Example { name, input, answers })
+        })
+        .collect::<Result<Vec<_>, AocError>>()?;
+
+    examples.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(examples)
+}
+
+/// Checks a solution against every example of `day` in `res` that records an answer for `part`.
+///
+/// # Panics
+///
+/// Panics if an example fails to parse or gives a different answer, or if no example has an
+/// answer for `part`.
+#[cfg(test)]
+pub(crate) fn check<S: crate::solution::Solution>(day: u8, part: crate::registry::Part) {
+    use crate::registry::Part;
+
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join(crate::input::DEFAULT_INPUTS_DIR);
+    let examples = read(&directory, day).unwrap_or_else(|error| panic!("{}", error));
+    let mut checked = 0;
+    for example in examples.iter() {
+        let Some(expected) = example.answers.part(part) else { continue };
+        let input = S::parse(&example.input).unwrap_or_else(|error| panic!("example '{}': {}", example.name, error));
+        let answer = match part {
+            Part::One => S::part1(&input),
+            Part::Two => S::part2(&input),
+        };
+        assert_eq!(expected, &answer, "day {} part {} of example '{}'", day, part.number(), example.name);
+        checked += 1;
+    }
+
+    assert!(checked > 0, "no example of day {} has an answer for part {}", day, part.number());
+}
+
+/// Generates the tests `example_part1` and `example_part2`, checking a solution against the
+/// examples of its day. Attributes in front of the solution are applied to both tests.
+#[cfg(test)]
+macro_rules! example_tests {
+    ($(#[$attribute:meta])* $solution:ty, $day:expr) => {
+        #[test]
+        $(#[$attribute])*
+        fn example_part1() {
+            $crate::examples::check::<$solution>($day, $crate::registry::Part::One);
+        }
+
+        #[test]
+        $(#[$attribute])*
+        fn example_part2() {
+            $crate::examples::check::<$solution>($day, $crate::registry::Part::Two);
+        }
+    };
+}
+
+#[cfg(test)]
+pub(crate) use example_tests;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::DEFAULT_INPUTS_DIR;
+    use crate::solution::Answer;
+
+    #[test]
+    fn read_examples() {
+        let examples = read(Path::new(DEFAULT_INPUTS_DIR), 12).unwrap();
+        assert_eq!(vec!["larger", "small"], examples.iter().map(|example| example.name.as_str()).collect::<Vec<_>>());
+        assert!(examples[1].input.starts_with("AAAA\n"));
+        assert_eq!(Some(Answer::Unsigned(140)), examples[1].answers.part1);
+        assert!(read(Path::new(DEFAULT_INPUTS_DIR), 25).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,91 @@
+use crate::client::HttpClient;
+use crate::error::AocError;
+use crate::input;
+use std::path::{Path, PathBuf};
+
+/// Whether an input was already present or had to be downloaded.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Downloads the input of `day` into the `res` layout below `directory`, unless it is already there.
+pub fn fetch(client: &impl HttpClient, session: &str, day: u8, directory: &Path) -> Result<Fetched, AocError> {
+    let path = input::input_path(directory, day);
+    if path.exists() {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let input = download(client, session, day)?;
+    let write_error = |error: std::io::Error| AocError::Input(format!("Could not write input {}: {}", path.display(), error));
+    std::fs::create_dir_all(path.parent().expect("input paths are inside a day directory")).map_err(write_error)?;
+    std::fs::write(&path, input).map_err(write_error)?;
+    Ok(Fetched::Downloaded(path))
+}
+
+/// Requests the input of `day` from the puzzle server.
+pub fn download(client: &impl HttpClient, session: &str, day: u8) -> Result<String, AocError> {
+    let response = client.get(&format!("/2024/day/{}/input", day), session)?;
+    match response.status {
+        200 => Ok(response.body),
+        400 | 401 | 403 => Err(AocError::Http(format!("Day {} input was refused ({}), the session token is probably invalid or expired", day, response.status))),
+        404 => Err(AocError::Http(format!("Day {} input is not available (404), the puzzle may not be unlocked yet", day))),
+        status => Err(AocError::Http(format!("Day {} input could not be downloaded ({}): {}", day, status, response.body.trim()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{mock, Response, UreqClient};
+    use std::cell::RefCell;
+
+    struct FakeClient {
+        response: Response,
+        requests: RefCell<Vec<String>>,
+    }
+
+    impl HttpClient for FakeClient {
+        fn get(&self, path: &str, _session: &str) -> Result<Response, AocError> {
+            self.requests.borrow_mut().push(path.to_string());
+            Ok(self.response.clone())
+        }
+
+        fn post(&self, _path: &str, _session: &str, _form: &[(&str, &str)]) -> Result<Response, AocError> {
+            unreachable!("fetching never posts")
+        }
+    }
+
+    fn fake(status: u16, body: &str) -> FakeClient {
+        FakeClient { response: Response { status, body: body.to_string() }, requests: RefCell::new(Vec::new()) }
+    }
+
+    #[test]
+    fn download_once_and_cache() {
+        let directory = std::env::temp_dir().join(format!("aoc2024-fetch-{}", std::process::id()));
+        let path = input::input_path(&directory, 3);
+        let client = fake(200, "mul(2,4)\n");
+
+        assert_eq!(Ok(Fetched::Downloaded(path.clone())), fetch(&client, "secret", 3, &directory));
+        assert_eq!(Ok(Fetched::Cached(path.clone())), fetch(&client, "secret", 3, &directory));
+        assert_eq!(vec!["/2024/day/3/input"], *client.requests.borrow());
+        assert_eq!("mul(2,4)\n", std::fs::read_to_string(&path).unwrap());
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn report_refused_downloads() {
+        assert!(download(&fake(400, "Puzzle inputs differ by user."), "expired", 1).unwrap_err().to_string().contains("session token"));
+        assert!(download(&fake(404, "Not Found"), "secret", 25).unwrap_err().to_string().contains("not available"));
+        assert!(matches!(download(&fake(500, "Oops"), "secret", 1), Err(AocError::Http(_))));
+    }
+
+    #[test]
+    fn download_from_server() {
+        let (url, server) = mock::serve(vec![(200, "3   4\n4   3\n")]);
+        assert_eq!(Ok("3   4\n4   3\n".to_string()), download(&UreqClient::new(url), "secret", 1));
+        assert!(server.join().unwrap()[0].contains("Cookie: session=secret"));
+    }
+}
@@ -0,0 +1,273 @@
+use crate::error::AocError;
+use crate::parsing;
+use crate::point::Point;
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+/// A rectangular grid stored row by row in a single vector, addressed by `(x, y)` with `(0, 0)` in
+/// the top left corner.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of cells does not match `width * height`.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(width * height, cells.len(), "a {}x{} grid needs {} cells", width, height, width * height);
+        Grid { cells, width, height }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self where T: Clone {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses one row per line, converting every character with `cell`. Rejected characters are
+    /// reported with `cell`'s message at their line and column.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Result<T, String>) -> Result<Self, AocError> {
+        let lines = parsing::grid_lines(input)?;
+        let width = lines[0].chars().count();
+        let height = lines.len();
+
+        let mut cells = Vec::with_capacity(width * height);
+        for line in lines {
+            for (index, c) in line.char_indices() {
+                cells.push(cell(c).map_err(|message| AocError::at(input, &line[index..], message))?);
+            }
+        }
+
+        Ok(Grid::new(width, height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether the possibly negative point lies within the grid.
+    pub fn contains(&self, point: Point) -> bool {
+        self.index_of(point).is_some()
+    }
+
+    /// The grid indices of `point`, or `None` if it lies outside of the grid.
+    pub fn index_of(&self, point: Point) -> Option<(usize, usize)> {
+        point.to_index().filter(|&(x, y)| x < self.width && y < self.height)
+    }
+
+    /// The cell at `point`, or `None` if it lies outside of the grid.
+    pub fn at(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|index| &self[index])
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The first position in row-major order whose cell matches `predicate`.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<(usize, usize)> {
+        self.iter().find(|(_, cell)| predicate(cell)).map(|(position, _)| position)
+    }
+
+    /// All positions whose cell matches `predicate`.
+    pub fn find_all<'a>(&'a self, predicate: impl Fn(&T) -> bool + 'a) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.iter().filter(move |(_, cell)| predicate(cell)).map(|(position, _)| position)
+    }
+
+    /// The horizontally and vertically adjacent positions inside the grid.
+    pub fn neighbors4(&self, (x, y): (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets((x, y), &Point::ORTHOGONAL)
+    }
+
+    /// The horizontally, vertically and diagonally adjacent positions inside the grid.
+    pub fn neighbors8(&self, (x, y): (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets((x, y), &Point::ADJACENT)
+    }
+
+    fn offsets<'a>(&'a self, position: (usize, usize), offsets: &'a [Point]) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let point = Point::from(position);
+        offsets.iter().filter_map(move |&offset| self.index_of(point + offset))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// All diagonals running from top left to bottom right, starting with the one in the bottom
+    /// left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (1..self.height).rev().map(|y| (0, y)).chain((0..self.width).map(|x| (x, 0)));
+        starts.map(|start| self.ray(start, Point::new(1, 1)))
+    }
+
+    /// All diagonals running from top right to bottom left, starting with the one in the top left
+    /// corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.width).map(|x| (x, 0)).chain((1..self.height).map(|y| (self.width - 1, y)));
+        starts.map(|start| self.ray(start, Point::new(-1, 1)))
+    }
+
+    /// The cells from `start` towards `direction` until leaving the grid.
+    pub fn ray(&self, start: (usize, usize), direction: Point) -> impl Iterator<Item = &T> {
+        let start = Point::from(start);
+        (0..).map_while(move |step| self.at(start + direction * step))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        self.get(x, y).unwrap_or_else(|| panic!("({}, {}) is outside of the {}x{} grid", x, y, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(x, y).unwrap_or_else(|| panic!("({}, {}) is outside of the {}x{} grid", x, y, width, height))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.at(point).unwrap_or_else(|| panic!("{:?} is outside of the {}x{} grid", point, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        let index = self.index_of(point).unwrap_or_else(|| panic!("{:?} is outside of the {}x{} grid", point, self.width, self.height));
+        &mut self[index]
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = AocError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Grid::parse(input, Ok)
+    }
+}
+
+/// Writes the grid as it is parsed, one line per row.
+impl Display for Grid<char> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::from_str("abc\ndef\n").unwrap()
+    }
+
+    #[test]
+    fn parse_and_access() {
+        let grid = grid();
+        assert_eq!("abc\ndef\n", grid.to_string());
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!('f', grid[(2, 1)]);
+        assert_eq!(None, grid.get(3, 0));
+        assert!(grid.contains(Point::new(2, 1)) && !grid.contains(Point::new(-1, 0)) && !grid.contains(Point::new(0, 2)));
+        assert_eq!(Some(&'e'), grid.at(Point::new(1, 1)));
+
+        let digits = Grid::parse("12\n3x", |c| c.to_digit(10).ok_or(format!("not a digit {:?}", c)));
+        assert_eq!(Some(AocError::parse(2, 2, "not a digit 'x'")), digits.err());
+    }
+
+    #[test]
+    fn mutate() {
+        let mut grid = Grid::filled(2, 2, 0);
+        grid[(1, 0)] = 5;
+        grid[Point::new(1, 1)] = 2;
+        *grid.get_mut(0, 1).unwrap() += 1;
+        assert_eq!(Grid::new(2, 2, vec![0, 5, 1, 2]), grid);
+        assert_eq!(Grid::new(2, 2, vec![false, true, true, true]), grid.map(|cell| *cell > 0));
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = grid();
+        assert_eq!(vec![(2, 0), (1, 1), (0, 0)], grid.neighbors4((1, 0)).collect::<Vec<_>>());
+        assert_eq!(2, grid.neighbors4((0, 0)).count());
+        assert_eq!(5, grid.neighbors8((1, 0)).count());
+        assert_eq!(3, grid.neighbors8((2, 1)).count());
+    }
+
+    #[test]
+    fn lines() {
+        let grid = grid();
+        let collect = |line: &mut dyn Iterator<Item = &char>| line.collect::<String>();
+        assert_eq!(vec!["abc", "def"], grid.rows().map(|row| row.iter().collect::<String>()).collect::<Vec<_>>());
+        assert_eq!(vec!["ad", "be", "cf"], grid.columns().map(|mut column| collect(&mut column)).collect::<Vec<_>>());
+        assert_eq!(vec!["d", "ae", "bf", "c"], grid.diagonals().map(|mut diagonal| collect(&mut diagonal)).collect::<Vec<_>>());
+        assert_eq!(vec!["a", "bd", "ce", "f"], grid.anti_diagonals().map(|mut diagonal| collect(&mut diagonal)).collect::<Vec<_>>());
+        assert_eq!("fb", collect(&mut grid.ray((2, 1), Point::new(-1, -1))));
+    }
+
+    #[test]
+    fn find_cells() {
+        let grid = grid();
+        assert_eq!(Some((1, 1)), grid.find(|c| *c == 'e'));
+        assert_eq!(None, grid.find(|c| *c == 'x'));
+        assert_eq!(vec![(0, 0), (1, 1)], grid.find_all(|c| "ae".contains(*c)).collect::<Vec<_>>());
+        assert_eq!(((2, 1), &'f'), grid.iter().last().unwrap());
+    }
+}
@@ -0,0 +1,171 @@
+use crate::error::AocError;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+pub const DEFAULT_INPUTS_DIR: &str = "res";
+pub const INPUTS_DIR_VARIABLE: &str = "AOC_INPUTS";
+
+/// Where to read a day's puzzle input from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+    /// An inputs directory in the `res` layout, falling back to the embedded inputs if enabled.
+    Directory(PathBuf),
+}
+
+impl Default for InputSource {
+    fn default() -> Self {
+        InputSource::Directory(default_inputs_dir())
+    }
+}
+
+/// The inputs directory configured through `AOC_INPUTS`, or `res` if it is not set.
+pub fn default_inputs_dir() -> PathBuf {
+    std::env::var_os(INPUTS_DIR_VARIABLE)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUTS_DIR))
+}
+
+impl InputSource {
+    /// Interprets a command line argument, where `-` stands for stdin.
+    pub fn from_argument(argument: &str) -> Self {
+        match argument {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(PathBuf::from(path)),
+        }
+    }
+
+    pub fn load(&self, day: u8) -> Result<String, AocError> {
+        let mut input = String::new();
+        self.open(day)?
+            .read_to_string(&mut input)
+            .map_err(|error| AocError::Input(format!("Could not read input {}: {}", self.describe(day), error)))?;
+        Ok(input)
+    }
+
+    /// Opens a day's input for reading it line by line, without loading all of it into memory.
+    pub fn open(&self, day: u8) -> Result<Box<dyn BufRead>, AocError> {
+        let path = match self {
+            InputSource::File(path) => path.clone(),
+            InputSource::Stdin => return Ok(Box::new(std::io::stdin().lock())),
+            InputSource::Directory(directory) => {
+                let path = input_path(directory, day);
+                if !path.exists() {
+                    if let Some(input) = embedded::input(day) {
+                        return Ok(Box::new(input.as_bytes()));
+                    }
+                }
+                path
+            }
+        };
+        let file = File::open(&path).map_err(|error| AocError::Input(format!("Could not read input {}: {}", path.display(), error)))?;
+        Ok(Box::new(BufReader::new(file)))
+    }
+
+    /// Where the input of `day` is read from, for error messages.
+    fn describe(&self, day: u8) -> String {
+        match self {
+            InputSource::File(path) => path.display().to_string(),
+            InputSource::Stdin => "from stdin".to_string(),
+            InputSource::Directory(directory) => input_path(directory, day).display().to_string(),
+        }
+    }
+
+    /// Loads the input of every distinct day in `days` once, to be shared by all parts of the day.
+    /// Stdin can only be read once, so every day gets the same input from it.
+    pub fn load_days(&self, days: impl IntoIterator<Item = u8>) -> HashMap<u8, Result<Arc<str>, AocError>> {
+        let mut inputs: HashMap<u8, Result<Arc<str>, AocError>> = HashMap::new();
+        for day in days {
+            if inputs.contains_key(&day) {
+                continue;
+            }
+            let input = match (self, inputs.values().next()) {
+                (InputSource::Stdin, Some(input)) => input.clone(),
+                _ => self.load(day).map(Arc::from),
+            };
+            inputs.insert(day, input);
+        }
+        inputs
+    }
+}
+
+/// The path of a day's input inside an inputs directory.
+pub fn input_path(directory: &Path, day: u8) -> PathBuf {
+    directory.join(format!("day{}", day)).join("part1")
+}
+
+#[cfg(feature = "embedded-inputs")]
+mod embedded {
+    pub fn input(day: u8) -> Option<&'static str> {
+        match day {
+            1 => Some(include_str!("../res/day1/part1")),
+            2 => Some(include_str!("../res/day2/part1")),
+            3 => Some(include_str!("../res/day3/part1")),
+            4 => Some(include_str!("../res/day4/part1")),
+            5 => Some(include_str!("../res/day5/part1")),
+            6 => Some(include_str!("../res/day6/part1")),
+            7 => Some(include_str!("../res/day7/part1")),
+            8 => Some(include_str!("../res/day8/part1")),
+            9 => Some(include_str!("../res/day9/part1")),
+            10 => Some(include_str!("../res/day10/part1")),
+            11 => Some(include_str!("../res/day11/part1")),
+            12 => Some(include_str!("../res/day12/part1")),
+            13 => Some(include_str!("../res/day13/part1")),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(not(feature = "embedded-inputs"))]
+mod embedded {
+    pub fn input(_day: u8) -> Option<&'static str> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_from_directory() {
+        let input = InputSource::Directory(PathBuf::from(DEFAULT_INPUTS_DIR)).load(11).unwrap();
+        assert!(!input.trim().is_empty());
+    }
+
+    #[test]
+    fn load_from_file() {
+        let path = input_path(Path::new(DEFAULT_INPUTS_DIR), 11);
+        let source = InputSource::from_argument(path.to_str().unwrap());
+        assert_eq!(InputSource::File(path.clone()), source);
+        assert_eq!(std::fs::read_to_string(path).unwrap(), source.load(11).unwrap());
+    }
+
+    #[test]
+    fn open_line_by_line() {
+        let source = InputSource::Directory(PathBuf::from(DEFAULT_INPUTS_DIR));
+        let lines = source.open(1).unwrap().lines().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(source.load(1).unwrap().lines().collect::<Vec<_>>(), lines);
+    }
+
+    #[test]
+    fn load_every_day_once() {
+        let source = InputSource::Directory(PathBuf::from(DEFAULT_INPUTS_DIR));
+        let inputs = source.load_days([11, 1, 11]);
+        assert_eq!(2, inputs.len());
+        assert_eq!(source.load(11).unwrap(), *inputs[&11].as_ref().unwrap().as_ref());
+        assert!(InputSource::File(PathBuf::from("does/not/exist")).load_days([3])[&3].is_err());
+    }
+
+    #[test]
+    fn report_missing_file() {
+        let source = InputSource::File(PathBuf::from("does/not/exist"));
+        assert!(source.load(1).unwrap_err().to_string().contains("does/not/exist"));
+        assert!(source.open(1).is_err());
+        assert_eq!(InputSource::Stdin, InputSource::from_argument("-"));
+    }
+}
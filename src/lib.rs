@@ -0,0 +1,36 @@
+//! Solutions for Advent of Code 2024.
+//!
+//! Every day lives in its own `dayN` module and implements [`solution::Solution`], which splits a
+//! puzzle into parsing and the two parts. The [`registry`] lists all implemented days so they can
+//! be run by number, and [`input`] locates the puzzle input for a day at runtime. Malformed inputs
+//! are reported as an [`error::AocError`] pointing at the offending line and column.
+
+pub mod bench;
+pub mod client;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod error;
+pub mod examples;
+pub mod fetch;
+pub mod grid;
+pub mod input;
+pub mod parsing;
+pub mod point;
+pub mod random;
+pub mod registry;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+pub mod verify;
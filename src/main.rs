@@ -1,39 +1,16 @@
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
+mod cli;
+mod output;
+
+use cli::Command;
 
 fn main() {
-    // day1::part1();
-    // day1::part2();
-    // day2::part1();
-    // day2::part2();
-    // day4::part1()
-    // day4::part2()
-    // day5::part1()
-    // day5::part2()
-    // day6::part2()
-    // day7::part2()
-    // day8::part1()
-    // day8::part2()
-    // day9::part1()
-    // day9::part2()
-    // day10::part1()
-    // day10::part2()
-    // day11::part1()
-    // day11::part2()
-    // day12::part1()
-    // day12::part2()
-    // day13::part1();
-    day13::part2();
-}
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let result = Command::parse(&args)
+        .map_err(|message| format!("{}\n\n{}", message, cli::USAGE))
+        .and_then(|command| command.execute());
 
+    if let Err(message) = result {
+        eprintln!("{}", message);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,202 @@
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How results are printed. `Table` is meant for people, the others for scripts.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Format {
+    #[default]
+    Table,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            other => Err(format!("Unknown format '{}', expected json, csv or table", other)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Text(String),
+    Integer(u128),
+    Null,
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Text(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Text(value)
+    }
+}
+
+impl From<u8> for Value {
+    fn from(value: u8) -> Self {
+        Value::Integer(value as u128)
+    }
+}
+
+impl From<usize> for Value {
+    fn from(value: usize) -> Self {
+        Value::Integer(value as u128)
+    }
+}
+
+/// Durations are written as whole nanoseconds.
+impl From<Duration> for Value {
+    fn from(value: Duration) -> Self {
+        Value::Integer(value.as_nanos())
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(Value::Null)
+    }
+}
+
+/// Rows of values under fixed column names, written as JSON or CSV.
+#[derive(Debug)]
+pub struct Records {
+    columns: &'static [&'static str],
+    rows: Vec<Vec<Value>>,
+}
+
+impl Records {
+    pub fn new(columns: &'static [&'static str]) -> Self {
+        Records { columns, rows: Vec::new() }
+    }
+
+    /// Adds a row with one value per column.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of values does not match the number of columns.
+    pub fn push(&mut self, row: Vec<Value>) {
+        assert_eq!(self.columns.len(), row.len(), "every column needs a value");
+        self.rows.push(row);
+    }
+
+    /// An array with one object per row. Answers are written as strings so that large numbers
+    /// survive parsers using doubles.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("[");
+        for (index, row) in self.rows.iter().enumerate() {
+            json.push_str(if index == 0 { "\n  {" } else { ",\n  {" });
+            for (column, (name, value)) in self.columns.iter().zip(row).enumerate() {
+                if column > 0 {
+                    json.push_str(", ");
+                }
+                write!(json, "{}: ", json_string(name)).unwrap();
+                match value {
+                    Value::Text(text) => json.push_str(&json_string(text)),
+                    Value::Integer(value) => write!(json, "{}", value).unwrap(),
+                    Value::Null => json.push_str("null"),
+                }
+            }
+            json.push('}');
+        }
+        json.push_str(if self.rows.is_empty() { "]" } else { "\n]" });
+        json
+    }
+
+    /// A header line followed by one line per row, quoting fields where needed. Missing values are
+    /// left empty.
+    pub fn to_csv(&self) -> String {
+        let mut csv = self.columns.iter().map(|name| csv_field(name)).collect::<Vec<_>>().join(",");
+        for row in self.rows.iter() {
+            csv.push('\n');
+            let fields = row.iter().map(|value| match value {
+                Value::Text(text) => csv_field(text),
+                Value::Integer(value) => value.to_string(),
+                Value::Null => String::new(),
+            });
+            csv.push_str(&fields.collect::<Vec<_>>().join(","));
+        }
+        csv
+    }
+
+    /// Prints the records in a machine readable format.
+    ///
+    /// # Panics
+    ///
+    /// Panics for [`Format::Table`], whose layout depends on the command.
+    pub fn print(&self, format: Format) {
+        match format {
+            Format::Json => println!("{}", self.to_json()),
+            Format::Csv => println!("{}", self.to_csv()),
+            Format::Table => unreachable!("tables are printed by the commands themselves"),
+        }
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Records {
+        let mut records = Records::new(&["day", "answer", "parse_ns", "status"]);
+        records.push(vec![1u8.into(), "1223326".into(), Duration::from_micros(2).into(), "ok".into()]);
+        records.push(vec![2u8.into(), Value::Null, Value::Null, "failed: line 1, \"x\"\n".into()]);
+        records
+    }
+
+    #[test]
+    fn write_json() {
+        assert_eq!(
+            "[\n  {\"day\": 1, \"answer\": \"1223326\", \"parse_ns\": 2000, \"status\": \"ok\"},\n  {\"day\": 2, \"answer\": null, \"parse_ns\": null, \"status\": \"failed: line 1, \\\"x\\\"\\n\"}\n]",
+            records().to_json()
+        );
+        assert_eq!("[]", Records::new(&["day"]).to_json());
+    }
+
+    #[test]
+    fn write_csv() {
+        assert_eq!("day,answer,parse_ns,status\n1,1223326,2000,ok\n2,,,\"failed: line 1, \"\"x\"\"\n\"", records().to_csv());
+    }
+
+    #[test]
+    fn parse_format() {
+        assert_eq!(Ok(Format::Json), "json".parse());
+        assert_eq!(Ok(Format::Table), "table".parse());
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
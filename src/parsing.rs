@@ -0,0 +1,81 @@
+use crate::error::AocError;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// Parses `token`, a slice of `input`, reporting its location in `input` if it is not a valid number.
+pub fn number<T: FromStr>(input: &str, token: &str) -> Result<T, AocError> {
+    token
+        .parse::<T>()
+        .map_err(|_| AocError::at(input, token, format!("invalid number '{}'", token)))
+}
+
+/// Calls `line` for every line of `reader` without reading all of it into memory, ending lines the
+/// same way as [`str::lines`]. Errors are moved from the line to their location in the input.
+pub fn read_lines(mut reader: impl BufRead, mut line: impl FnMut(&str) -> Result<(), AocError>) -> Result<(), AocError> {
+    let mut buffer = String::new();
+    for number in 1.. {
+        buffer.clear();
+        let read = reader
+            .read_line(&mut buffer)
+            .map_err(|error| AocError::Input(format!("Could not read line {}: {}", number, error)))?;
+        if read == 0 {
+            break;
+        }
+        let text = buffer.strip_suffix('\n').map(|text| text.strip_suffix('\r').unwrap_or(text)).unwrap_or(&buffer);
+        line(text).map_err(|error| error.on_line(number))?;
+    }
+    Ok(())
+}
+
+/// Splits `input` into its lines, requiring at least one line and all lines to have the same length.
+pub fn grid_lines(input: &str) -> Result<Vec<&str>, AocError> {
+    let lines = input.lines().collect::<Vec<_>>();
+    let width = match lines.first() {
+        Some(line) if !line.is_empty() => line.chars().count(),
+        _ => return Err(AocError::parse(1, 1, "empty grid")),
+    };
+
+    for line in lines.iter() {
+        let length = line.chars().count();
+        if length != width {
+            let mismatch = line.char_indices().nth(width).map(|(index, _)| index).unwrap_or(line.len());
+            return Err(AocError::at(input, &line[mismatch..], format!("expected {} columns, found {}", width, length)));
+        }
+    }
+
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_invalid_number() {
+        let input = "1 2\n3 x4";
+        assert_eq!(Ok(2), number::<u32>(input, &input[2..3]));
+        assert_eq!(Err(AocError::parse(2, 3, "invalid number 'x4'")), number::<u32>(input, &input[6..]));
+    }
+
+    #[test]
+    fn read_lines_like_str_lines() {
+        let input = "1 2\r\n\n3 4";
+        let mut lines = Vec::new();
+        let numbers = |line: &str| line.split_whitespace().try_for_each(|token| number::<u32>(line, token).map(|_| ()));
+        read_lines(input.as_bytes(), |line| {
+            lines.push(line.to_string());
+            numbers(line)
+        })
+        .unwrap();
+        assert_eq!(input.lines().collect::<Vec<_>>(), lines);
+        assert_eq!(Err(AocError::parse(2, 3, "invalid number 'x4'")), read_lines("1 2\n3 x4\n".as_bytes(), numbers));
+    }
+
+    #[test]
+    fn require_rectangular_grid() {
+        assert_eq!(Ok(vec!["ab", "cd"]), grid_lines("ab\ncd\n"));
+        assert_eq!(Err(AocError::parse(2, 2, "expected 2 columns, found 1")), grid_lines("ab\nc\nef"));
+        assert_eq!(Err(AocError::parse(3, 3, "expected 2 columns, found 3")), grid_lines("ab\ncd\nefg"));
+        assert_eq!(Err(AocError::parse(1, 1, "empty grid")), grid_lines(""));
+    }
+}
@@ -0,0 +1,196 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A position or offset on the plane. `y` grows downwards, matching the rows of a [`Grid`].
+///
+/// [`Grid`]: crate::grid::Grid
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+    pub const UP: Point = Point::new(0, -1);
+    pub const RIGHT: Point = Point::new(1, 0);
+    pub const DOWN: Point = Point::new(0, 1);
+    pub const LEFT: Point = Point::new(-1, 0);
+
+    /// The four orthogonal directions, clockwise starting with [`Point::UP`].
+    pub const ORTHOGONAL: [Point; 4] = [Point::UP, Point::RIGHT, Point::DOWN, Point::LEFT];
+
+    /// All eight directions including the diagonals, clockwise starting with [`Point::UP`].
+    pub const ADJACENT: [Point; 8] = [
+        Point::new(0, -1), Point::new(1, -1), Point::new(1, 0), Point::new(1, 1),
+        Point::new(0, 1), Point::new(-1, 1), Point::new(-1, 0), Point::new(-1, -1),
+    ];
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    /// The sum of the absolute coordinate differences.
+    pub fn manhattan(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The shortest vector with the same direction, dividing both coordinates by their greatest
+    /// common divisor. The origin stays the origin.
+    pub fn normalized(self) -> Point {
+        match gcd(self.x, self.y) {
+            0 => self,
+            divisor => Point::new(self.x / divisor, self.y / divisor),
+        }
+    }
+
+    /// Rotates by 90 degrees clockwise as seen on screen, turning [`Point::UP`] into [`Point::RIGHT`].
+    pub fn rotate_right(self) -> Point {
+        Point::new(-self.y, self.x)
+    }
+
+    /// Rotates by 90 degrees counterclockwise as seen on screen, turning [`Point::UP`] into [`Point::LEFT`].
+    pub fn rotate_left(self) -> Point {
+        Point::new(self.y, -self.x)
+    }
+
+    /// The four orthogonally adjacent points in the order of [`Point::ORTHOGONAL`].
+    pub fn neighbors4(self) -> [Point; 4] {
+        Point::ORTHOGONAL.map(|direction| self + direction)
+    }
+
+    /// `self + other`, or `None` if a coordinate overflows.
+    pub fn checked_add(self, other: Point) -> Option<Point> {
+        Some(Point::new(self.x.checked_add(other.x)?, self.y.checked_add(other.y)?))
+    }
+
+    /// `self - other`, or `None` if a coordinate overflows.
+    pub fn checked_sub(self, other: Point) -> Option<Point> {
+        Some(Point::new(self.x.checked_sub(other.x)?, self.y.checked_sub(other.y)?))
+    }
+
+    /// `self * value`, or `None` if a coordinate overflows.
+    pub fn checked_mul(self, value: i64) -> Option<Point> {
+        Some(Point::new(self.x.checked_mul(value)?, self.y.checked_mul(value)?))
+    }
+
+    /// Converts to `(x, y)` grid indices, or `None` if a coordinate is negative.
+    pub fn to_index(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+    }
+}
+
+/// The non-negative greatest common divisor, `0` only if both values are `0`.
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Point::new(x as i64, y as i64)
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Self;
+
+    fn mul(self, value: i64) -> Self::Output {
+        Point::new(self.x * value, self.y * value)
+    }
+}
+
+impl Neg for Point {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let mut point = Point::new(3, -2);
+        assert_eq!(Point::new(4, -4), point + Point::new(1, -2));
+        assert_eq!(Point::new(2, 0), point - Point::new(1, -2));
+        assert_eq!(Point::new(-9, 6), point * -3);
+        assert_eq!(Point::new(-3, 2), -point);
+
+        point += Point::RIGHT;
+        point -= Point::UP;
+        assert_eq!(Point::new(4, -1), point);
+        assert_eq!(7, Point::ORIGIN.manhattan(Point::new(-3, 4)));
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        assert_eq!(Some(Point::new(4, -4)), Point::new(3, -2).checked_add(Point::new(1, -2)));
+        assert_eq!(Some(Point::new(-9, 6)), Point::new(3, -2).checked_mul(-3));
+        assert_eq!(None, Point::new(1, i64::MAX).checked_add(Point::DOWN));
+        assert_eq!(None, Point::new(i64::MIN, 0).checked_sub(Point::RIGHT));
+        assert_eq!(None, Point::new(2, i64::MAX / 2 + 1).checked_mul(2));
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!(Point::new(2, -3), Point::new(8, -12).normalized());
+        assert_eq!(Point::new(0, 1), Point::new(0, 7).normalized());
+        assert_eq!(Point::ORIGIN, Point::ORIGIN.normalized());
+        assert_eq!(6, gcd(-12, 18));
+    }
+
+    #[test]
+    fn rotate() {
+        assert_eq!(Point::RIGHT, Point::UP.rotate_right());
+        assert_eq!(Point::LEFT, Point::UP.rotate_left());
+        assert_eq!([Point::RIGHT, Point::DOWN, Point::LEFT, Point::UP], Point::ORTHOGONAL.map(Point::rotate_right));
+        assert_eq!(Point::new(2, 1), Point::new(1, -2).rotate_right().rotate_left().rotate_right());
+    }
+
+    #[test]
+    fn grid_indices() {
+        assert_eq!(Some((3, 0)), Point::new(3, 0).to_index());
+        assert_eq!(None, Point::new(3, -1).to_index());
+        assert_eq!(Point::new(1, 2), Point::from((1usize, 2usize)));
+    }
+}
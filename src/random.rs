@@ -0,0 +1,115 @@
+use std::ops::RangeInclusive;
+
+/// A small, seedable pseudo random number generator (SplitMix64), good enough for generating
+/// puzzle inputs but not for anything security related.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, all equally likely.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        assert!(!range.is_empty(), "cannot pick from an empty range");
+        let span = (range.end() - range.start()).wrapping_add(1);
+        if span == 0 {
+            return self.next_u64();
+        }
+        // Rejecting the top of the range avoids favouring small numbers.
+        let limit = u64::MAX - u64::MAX % span;
+        loop {
+            let value = self.next_u64();
+            if value < limit {
+                return range.start() + value % span;
+            }
+        }
+    }
+
+    /// An index below `len`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is 0.
+    pub fn index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "cannot pick an index of nothing");
+        self.range(0..=len as u64 - 1) as usize
+    }
+
+    /// True with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64) / ((1u64 << 53) as f64) < probability
+    }
+
+    /// A random element of `items`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `items` is empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.index(index + 1));
+        }
+    }
+}
+
+/// Calls `property` with a differently seeded generator for each of `cases` cases, reporting the
+/// seed of the first failing case so it can be reproduced.
+#[cfg(test)]
+pub(crate) fn check(cases: u64, mut property: impl FnMut(&mut Rng)) {
+    for seed in 0..cases {
+        let mut rng = Rng::new(seed);
+        if let Err(payload) = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| property(&mut rng))) {
+            panic!("property failed for seed {}: {}", seed, crate::runner::panic_message(payload.as_ref()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deterministic_for_a_seed() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(7), numbers(7));
+        assert_ne!(numbers(7), numbers(8));
+    }
+
+    #[test]
+    fn stay_in_range() {
+        let mut rng = Rng::new(1);
+        let values = (0..1000).map(|_| rng.range(3..=6)).collect::<Vec<_>>();
+        assert!(values.iter().all(|value| (3..=6).contains(value)));
+        assert!((3..=6).all(|value| values.contains(&value)));
+        assert_eq!(5, rng.range(5..=5));
+        rng.range(0..=u64::MAX);
+
+        let mut items = (0..10).collect::<Vec<_>>();
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!((0..10).collect::<Vec<_>>(), items);
+    }
+}
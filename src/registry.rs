@@ -0,0 +1,180 @@
+use crate::error::AocError;
+use crate::random::Rng;
+use crate::solution::{Answer, Solution};
+use std::io::BufRead;
+use std::time::{Duration, Instant};
+use crate::{day1, day10, day11, day12, day13, day2, day3, day4, day5, day6, day7, day8, day9};
+
+/// A registered day, solving its puzzle through the day's [`Solution`].
+#[derive(Debug)]
+pub struct Day {
+    pub number: u8,
+    pub title: &'static str,
+    run: fn(&str, Part) -> Result<Run, AocError>,
+    validate: fn(&str) -> Result<(), AocError>,
+    generate: fn(&mut Rng, usize) -> String,
+    stream: fn(&mut dyn BufRead, Part) -> Option<Result<Answer, AocError>>,
+}
+
+/// The answer of a single part together with the time spent parsing the input and solving it.
+#[derive(Clone, Debug)]
+pub struct Run {
+    pub answer: Answer,
+    /// `None` for days timed as a whole, see [`Solution::COMBINED_TIMING`], whose solve time then
+    /// includes parsing.
+    pub parse_time: Option<Duration>,
+    pub solve_time: Duration,
+}
+
+impl Run {
+    /// The time spent parsing and solving together.
+    pub fn total_time(&self) -> Duration {
+        self.parse_time.unwrap_or_default() + self.solve_time
+    }
+}
+
+/// All implemented days, ordered by their number.
+pub const DAYS: [Day; 13] = [
+    Day { number: 1, title: "Historian Hysteria", run: run::<day1::HistorianHysteria>, validate: validate::<day1::HistorianHysteria>, generate: day1::HistorianHysteria::generate, stream: day1::HistorianHysteria::stream },
+    Day { number: 2, title: "Red-Nosed Reports", run: run::<day2::RedNosedReports>, validate: validate::<day2::RedNosedReports>, generate: day2::RedNosedReports::generate, stream: day2::RedNosedReports::stream },
+    Day { number: 3, title: "Mull It Over", run: run::<day3::MullItOver>, validate: validate::<day3::MullItOver>, generate: day3::MullItOver::generate, stream: day3::MullItOver::stream },
+    Day { number: 4, title: "Ceres Search", run: run::<day4::CeresSearch>, validate: validate::<day4::CeresSearch>, generate: day4::CeresSearch::generate, stream: day4::CeresSearch::stream },
+    Day { number: 5, title: "Print Queue", run: run::<day5::PrintQueue>, validate: validate::<day5::PrintQueue>, generate: day5::PrintQueue::generate, stream: day5::PrintQueue::stream },
+    Day { number: 6, title: "Guard Gallivant", run: run::<day6::GuardGallivant>, validate: validate::<day6::GuardGallivant>, generate: day6::GuardGallivant::generate, stream: day6::GuardGallivant::stream },
+    Day { number: 7, title: "Bridge Repair", run: run::<day7::BridgeRepair>, validate: validate::<day7::BridgeRepair>, generate: day7::BridgeRepair::generate, stream: day7::BridgeRepair::stream },
+    Day { number: 8, title: "Resonant Collinearity", run: run::<day8::ResonantCollinearity>, validate: validate::<day8::ResonantCollinearity>, generate: day8::ResonantCollinearity::generate, stream: day8::ResonantCollinearity::stream },
+    Day { number: 9, title: "Disk Fragmenter", run: run::<day9::DiskFragmenter>, validate: validate::<day9::DiskFragmenter>, generate: day9::DiskFragmenter::generate, stream: day9::DiskFragmenter::stream },
+    Day { number: 10, title: "Hoof It", run: run::<day10::HoofIt>, validate: validate::<day10::HoofIt>, generate: day10::HoofIt::generate, stream: day10::HoofIt::stream },
+    Day { number: 11, title: "Plutonian Pebbles", run: run::<day11::PlutonianPebbles>, validate: validate::<day11::PlutonianPebbles>, generate: day11::PlutonianPebbles::generate, stream: day11::PlutonianPebbles::stream },
+    Day { number: 12, title: "Garden Groups", run: run::<day12::GardenGroups>, validate: validate::<day12::GardenGroups>, generate: day12::GardenGroups::generate, stream: day12::GardenGroups::stream },
+    Day { number: 13, title: "Claw Contraption", run: run::<day13::ClawContraption>, validate: validate::<day13::ClawContraption>, generate: day13::ClawContraption::generate, stream: day13::ClawContraption::stream },
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+impl Day {
+    /// Parses `input` and solves the given part of it.
+    pub fn solve(&self, input: &str, part: Part) -> Result<Answer, AocError> {
+        self.run(input, part).map(|run| run.answer)
+    }
+
+    /// Parses `input` and solves the given part of it, measuring both steps separately.
+    pub fn run(&self, input: &str, part: Part) -> Result<Run, AocError> {
+        (self.run)(input, part)
+    }
+
+    /// Parses `input` without solving it, reporting where it does not match the puzzle's format.
+    pub fn validate(&self, input: &str) -> Result<(), AocError> {
+        (self.validate)(input)
+    }
+
+    /// Solves `part` while reading the input line by line, see [`Solution::stream`].
+    pub fn stream(&self, reader: &mut dyn BufRead, part: Part) -> Option<Result<Answer, AocError>> {
+        (self.stream)(reader, part)
+    }
+
+    /// Generates a random input for the day, see [`Solution::generate`].
+    pub fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        (self.generate)(rng, size)
+    }
+}
+
+fn run<S: Solution>(input: &str, part: Part) -> Result<Run, AocError> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parse_time = start.elapsed();
+
+    let solve_start = Instant::now();
+    let answer = match part {
+        Part::One => S::part1(&input),
+        Part::Two => S::part2(&input),
+    };
+
+    if S::COMBINED_TIMING {
+        Ok(Run { answer, parse_time: None, solve_time: start.elapsed() })
+    } else {
+        Ok(Run { answer, parse_time: Some(parse_time), solve_time: solve_start.elapsed() })
+    }
+}
+
+fn validate<S: Solution>(input: &str) -> Result<(), AocError> {
+    S::parse(input).map(|_| ())
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Part {
+    One,
+    Two
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random;
+
+    #[test]
+    fn days_are_registered_in_order() {
+        assert!(DAYS.windows(2).all(|days| days[0].number < days[1].number));
+        assert!(find(13).is_some());
+        assert!(find(0).is_none());
+    }
+
+    #[test]
+    fn time_days_parsing_in_one_pass_as_a_whole() {
+        let run = find(1).unwrap().run("3   4\n4   3\n", Part::Two).unwrap();
+        assert_eq!((Answer::Unsigned(7), None), (run.answer, run.parse_time));
+        assert!(find(11).unwrap().run("125 17", Part::One).unwrap().parse_time.is_some());
+    }
+
+    #[test]
+    fn validate_damaged_examples_without_panicking() {
+        const DAMAGE: [char; 12] = ['\n', ' ', ',', '|', ':', '0', '9', '-', '+', 'X', '#', '^'];
+        let directory = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(crate::input::DEFAULT_INPUTS_DIR);
+        for day in DAYS.iter() {
+            let examples = crate::examples::read(&directory, day.number).unwrap();
+            random::check(200, |rng| {
+                let mut input = rng.pick(&examples).input.chars().collect::<Vec<_>>();
+                for _ in 0..rng.range(1..=3) {
+                    let index = rng.index(input.len() + 1);
+                    match rng.range(0..=2) {
+                        0 if index < input.len() => drop(input.remove(index)),
+                        1 if index < input.len() => input[index] = *rng.pick(&DAMAGE),
+                        _ => input.insert(index, *rng.pick(&DAMAGE)),
+                    }
+                }
+                let _ = day.validate(&input.into_iter().collect::<String>());
+            });
+        }
+    }
+
+    #[test]
+    fn generated_inputs_can_be_solved() {
+        for day in DAYS.iter() {
+            for seed in 0..3 {
+                let input = day.generate(&mut Rng::new(seed), 5);
+                if input.is_empty() {
+                    continue;
+                }
+                assert_eq!(input, day.generate(&mut Rng::new(seed), 5), "day {} is not deterministic", day.number);
+                for part in Part::ALL {
+                    if let Err(error) = day.solve(&input, part) {
+                        panic!("day {} with seed {}: {}\n{}", day.number, seed, error, input);
+                    }
+                }
+            }
+        }
+    }
+}
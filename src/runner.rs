@@ -0,0 +1,176 @@
+use crate::input::InputSource;
+use crate::registry::{Day, Part, Run};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+
+/// How running a single part ended.
+#[derive(Clone, Debug)]
+pub enum Outcome {
+    Solved(Run),
+    /// The input could not be loaded or parsed.
+    Failed(String),
+    Panicked(String),
+    /// The solution was still running when the timeout passed.
+    TimedOut(Duration),
+}
+
+/// Why [`isolate`] returned without a result.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Interruption {
+    Panicked(String),
+    TimedOut(Duration),
+}
+
+/// The outcome of running one part of a day.
+#[derive(Clone, Debug)]
+pub struct Report {
+    pub day: &'static Day,
+    pub part: Part,
+    pub outcome: Outcome,
+}
+
+/// Runs every part on `jobs` threads, loading the input of each day once from `source`, and reports
+/// the outcomes in the order of `parts`. Solutions that panic or run longer than `timeout` are reported instead
+/// of ending the run.
+pub fn run_all(parts: &[(&'static Day, Part)], source: &InputSource, jobs: usize, timeout: Option<Duration>) -> Vec<Report> {
+    let inputs = source.load_days(parts.iter().map(|(day, _)| day.number));
+    parallel_map(parts, jobs, |&(day, part)| match &inputs[&day.number] {
+        Ok(input) => Ok(run(day, part, input.clone(), timeout)),
+        Err(error) => Err(error.to_string()),
+    })
+    .into_iter()
+    .zip(parts)
+    .map(|(result, &(day, part))| {
+        let outcome = match result {
+            Ok(Ok(outcome)) => outcome,
+            Ok(Err(message)) => Outcome::Failed(message),
+            Err(message) => Outcome::Panicked(message),
+        };
+        Report { day, part, outcome }
+    })
+    .collect()
+}
+
+/// Solves `part` of `day` from `input` on a thread of its own, reporting a panic or a run longer
+/// than `timeout` as its outcome.
+pub fn run(day: &'static Day, part: Part, input: Arc<str>, timeout: Option<Duration>) -> Outcome {
+    match isolate(timeout, move || day.run(&input, part)) {
+        Ok(Ok(run)) => Outcome::Solved(run),
+        Ok(Err(error)) => Outcome::Failed(error.to_string()),
+        Err(Interruption::Panicked(message)) => Outcome::Panicked(message),
+        Err(Interruption::TimedOut(timeout)) => Outcome::TimedOut(timeout),
+    }
+}
+
+/// Calls `f` on a thread of its own, catching a panic and giving up on it after `timeout`.
+///
+/// A thread that timed out cannot be stopped, so it is left running in the background until it
+/// finishes or the process exits.
+pub fn isolate<R: Send + 'static>(timeout: Option<Duration>, f: impl FnOnce() -> R + Send + 'static) -> Result<R, Interruption> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| panic_message(payload.as_ref()));
+        // The receiver is gone if the caller stopped waiting, in which case the result is dropped.
+        let _ = sender.send(result);
+    });
+
+    let result = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout),
+        None => receiver.recv().map_err(Into::into),
+    };
+    match result {
+        Ok(result) => result.map_err(Interruption::Panicked),
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(Interruption::TimedOut(timeout.expect("only waiting with a timeout times out"))),
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(Interruption::Panicked("the thread ended without a result".to_string())),
+    }
+}
+
+/// Applies `f` to every item on `jobs` threads, returning the results in the order of `items`.
+/// A panic in `f` is caught and returned as its message.
+///
+/// # Panics
+///
+/// Panics if `jobs` is 0.
+pub fn parallel_map<T: Sync, R: Send>(items: &[T], jobs: usize, f: impl Fn(&T) -> R + Sync) -> Vec<Result<R, String>> {
+    assert!(jobs > 0, "at least one job is needed");
+
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new((0..items.len()).map(|_| None).collect::<Vec<_>>());
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else { break };
+                let result = panic::catch_unwind(AssertUnwindSafe(|| f(item))).map_err(|payload| panic_message(payload.as_ref()));
+                results.lock().unwrap_or_else(|poisoned| poisoned.into_inner())[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .into_iter()
+        .map(|result| result.expect("every item is processed"))
+        .collect()
+}
+
+/// The message a panic was raised with.
+pub fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::DEFAULT_INPUTS_DIR;
+    use crate::registry;
+    use std::path::PathBuf;
+
+    #[test]
+    fn keep_order_and_catch_panics() {
+        let items = (0..20).collect::<Vec<u64>>();
+        let results = parallel_map(&items, 4, |&item| {
+            if item == 7 {
+                panic!("unlucky {}", item);
+            }
+            item * 2
+        });
+
+        assert_eq!(Err("unlucky 7".to_string()), results[7]);
+        assert_eq!((0..20).filter(|item| *item != 7).map(|item| Ok(item * 2)).collect::<Vec<_>>(), results.into_iter().filter(|result| result.is_ok()).collect::<Vec<_>>());
+        assert!(parallel_map(&[] as &[u8], 3, |_| ()).is_empty());
+    }
+
+    #[test]
+    fn isolate_panics_and_slow_calls() {
+        assert_eq!(Ok(42), isolate(None, || 42));
+        assert_eq!(Ok(42), isolate(Some(Duration::from_secs(10)), || 42));
+        assert_eq!(Err(Interruption::Panicked("No guard found".to_string())), isolate(None, || -> u8 { panic!("No guard found") }));
+
+        let timeout = Duration::from_millis(20);
+        let slow = || std::thread::sleep(Duration::from_secs(5));
+        assert_eq!(Err(Interruption::TimedOut(timeout)), isolate(Some(timeout), slow));
+    }
+
+    #[test]
+    fn run_days_in_parallel() {
+        let parts = [(registry::find(11).unwrap(), Part::One), (registry::find(3).unwrap(), Part::Two)];
+        let reports = run_all(&parts, &InputSource::Directory(PathBuf::from(DEFAULT_INPUTS_DIR)), 2, None);
+        assert_eq!(vec![(11, Part::One), (3, Part::Two)], reports.iter().map(|report| (report.day.number, report.part)).collect::<Vec<_>>());
+        assert!(reports.iter().all(|report| matches!(report.outcome, Outcome::Solved(_))));
+
+        let reports = run_all(&parts, &InputSource::Directory(PathBuf::from("does/not/exist")), 2, None);
+        assert!(reports.iter().all(|report| matches!(report.outcome, Outcome::Failed(_))));
+
+        let parts = [(registry::find(6).unwrap(), Part::Two)];
+        let reports = run_all(&parts, &InputSource::Directory(PathBuf::from(DEFAULT_INPUTS_DIR)), 1, Some(Duration::from_millis(1)));
+        assert!(matches!(reports[0].outcome, Outcome::TimedOut(_)));
+    }
+}
@@ -0,0 +1,214 @@
+use crate::error::AocError;
+use crate::{examples, verify};
+use std::path::{Path, PathBuf};
+
+/// Creates the module and inputs directory of a new day inside the project at `root` and
+/// registers it, returning the files created or changed. Without a `title` the day is called
+/// `Day N`.
+pub fn scaffold(root: &Path, day: u8, title: Option<&str>) -> Result<Vec<PathBuf>, AocError> {
+    if !(1..=25).contains(&day) {
+        return Err(AocError::Input(format!("Day {} is not part of the calendar", day)));
+    }
+
+    let title = title.map(str::to_string).unwrap_or_else(|| format!("Day {}", day));
+    let name = type_name(&title)
+        .filter(|name| name.starts_with(|c: char| c.is_ascii_alphabetic()))
+        .ok_or_else(|| AocError::Input(format!("Cannot derive a type name from the title '{}'", title)))?;
+
+    let module_path = root.join("src").join(format!("day{}", day)).join("mod.rs");
+    if module_path.exists() {
+        return Err(AocError::Input(format!("{} already exists", module_path.display())));
+    }
+
+    let lib_path = root.join("src").join("lib.rs");
+    let registry_path = root.join("src").join("registry.rs");
+    let lib = register_module(&read(&lib_path)?, day)?;
+    let registry = register_day(&read(&registry_path)?, day, &title, &name)?;
+    let inputs_dir = root.join(crate::input::DEFAULT_INPUTS_DIR);
+    let answers_path = verify::answers_path(&inputs_dir, day);
+    let example_path = examples::examples_dir(&inputs_dir, day).join("example.txt");
+
+    write(&module_path, &module_template(day, &title, &name))?;
+    write(&lib_path, &lib)?;
+    write(&registry_path, &registry)?;
+    if !answers_path.exists() {
+        write(&answers_path, "# Record the answers once they are accepted, e.g.\n# part1: 1234\n")?;
+    }
+    if !example_path.exists() {
+        write(&example_path, "")?;
+        write(&example_path.with_extension("answers"), "# The answers the puzzle gives for example.txt, e.g.\n# part1: 0\n# part2: 0\n")?;
+    }
+
+    Ok(vec![module_path, lib_path, registry_path, answers_path, example_path])
+}
+
+/// The title in upper camel case, e.g. `RestroomRedoubt` for `Restroom Redoubt`.
+fn type_name(title: &str) -> Option<String> {
+    let name = title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word[..1].to_ascii_uppercase() + &word[1..])
+        .collect::<String>();
+    Some(name).filter(|name| !name.is_empty())
+}
+
+/// Adds `pub mod dayN;` after the existing day modules of `lib.rs`.
+fn register_module(lib: &str, day: u8) -> Result<String, AocError> {
+    let declaration = format!("pub mod day{};", day);
+    if lib.lines().any(|line| line.trim() == declaration) {
+        return Err(AocError::Input(format!("day{} is already declared in lib.rs", day)));
+    }
+    insert_after_last(lib, |line| line.starts_with("pub mod day"), &declaration)
+        .ok_or_else(|| AocError::Input("No day modules found in lib.rs".to_string()))
+}
+
+/// Imports the module into `registry.rs` and adds the day to `DAYS`, which stays ordered by number
+/// even when days are scaffolded out of order.
+fn register_day(registry: &str, day: u8, title: &str, name: &str) -> Result<String, AocError> {
+    let missing = |what: &str| AocError::Input(format!("No {} found in registry.rs", what));
+
+    let mut lines = registry.lines().map(str::to_string).collect::<Vec<_>>();
+
+    let import = lines
+        .iter_mut()
+        .find(|line| line.starts_with("use crate::{day"))
+        .ok_or_else(|| missing("day import"))?;
+    let mut modules = import["use crate::{".len()..import.len() - "};".len()].split(", ").map(str::to_string).collect::<Vec<_>>();
+    modules.push(format!("day{}", day));
+    modules.sort();
+    *import = format!("use crate::{{{}}};", modules.join(", "));
+
+    let days = lines
+        .iter_mut()
+        .find(|line| line.starts_with("pub const DAYS: [Day; "))
+        .ok_or_else(|| missing("DAYS array"))?;
+    let count = days["pub const DAYS: [Day; ".len()..].split(']').next().and_then(|count| count.parse::<usize>().ok()).ok_or_else(|| missing("DAYS length"))?;
+    *days = format!("pub const DAYS: [Day; {}] = [", count + 1);
+
+    let entry = format!(
+        "    Day {{ number: {day}, title: {title:?}, run: run::<day{day}::{name}>, validate: validate::<day{day}::{name}>, generate: day{day}::{name}::generate, stream: day{day}::{name}::stream }},"
+    );
+    let registry = lines.join("\n") + "\n";
+    let precedes = |line: &str| {
+        let number = line.trim_start().strip_prefix("Day { number: ").and_then(|rest| rest.split(',').next()?.parse::<u8>().ok());
+        number.is_some_and(|number| number < day)
+    };
+    insert_after_last(&registry, precedes, &entry)
+        .or_else(|| insert_after_last(&registry, |line| line.starts_with("pub const DAYS: [Day; "), &entry))
+        .ok_or_else(|| missing("DAYS array"))
+}
+
+fn insert_after_last(text: &str, predicate: impl Fn(&str) -> bool, line: &str) -> Option<String> {
+    let mut lines = text.lines().collect::<Vec<_>>();
+    let index = lines.iter().rposition(|line| predicate(line))?;
+    lines.insert(index + 1, line);
+    Some(lines.join("\n") + "\n")
+}
+
+fn module_template(day: u8, title: &str, name: &str) -> String {
+    format!(r#"//! Day {day}: {title}.
+
+use crate::error::AocError;
+use crate::random::Rng;
+use crate::solution::{{Answer, Solution}};
+
+/// TODO: describe what parts 1 and 2 compute.
+pub struct {name};
+
+impl Solution for {name} {{
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(_input: &str) -> Result<Self::Input<'_>, AocError> {{
+        // TODO: parse the input, e.g. Ok(input.lines().collect()), once the parts are solved.
+        Err(AocError::Input("Day {day} is not implemented yet".to_string()))
+    }}
+
+    fn part1(input: &Self::Input<'_>) -> Answer {{
+        // TODO: solve part 1, which is not reached while parse reports the day as not implemented.
+        input.len().into()
+    }}
+
+    fn part2(input: &Self::Input<'_>) -> Answer {{
+        // TODO: solve part 2.
+        input.len().into()
+    }}
+
+    fn generate(_rng: &mut Rng, _size: usize) -> String {{
+        // TODO: generate random inputs, an empty one means the day has no generator yet.
+        String::new()
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    // Remove the ignore attribute once res/day{day}/examples holds the example and its answers.
+    crate::examples::example_tests!(#[ignore = "example not filled in yet"] {name}, {day});
+}}
+"#)
+}
+
+fn read(path: &Path) -> Result<String, AocError> {
+    std::fs::read_to_string(path).map_err(|error| AocError::Input(format!("Could not read {}: {}", path.display(), error)))
+}
+
+fn write(path: &Path, content: &str) -> Result<(), AocError> {
+    let write_error = |error: std::io::Error| AocError::Input(format!("Could not write {}: {}", path.display(), error));
+    std::fs::create_dir_all(path.parent().expect("scaffolded files are inside a directory")).map_err(write_error)?;
+    std::fs::write(path, content).map_err(write_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derive_type_names() {
+        assert_eq!(Some("RestroomRedoubt".to_string()), type_name("Restroom Redoubt"));
+        assert_eq!(Some("RedNosedReports".to_string()), type_name("Red-Nosed Reports"));
+        assert_eq!(None, type_name(" - "));
+    }
+
+    #[test]
+    fn register_in_lib_and_registry() {
+        let lib = "pub mod day1;\npub mod day2;\npub mod error;\n";
+        assert_eq!("pub mod day1;\npub mod day2;\npub mod day3;\npub mod error;\n", register_module(lib, 3).unwrap());
+        assert!(register_module(lib, 2).is_err());
+
+        let registry = "use crate::{day1, day10, day2, day9};\n\npub const DAYS: [Day; 2] = [\n    Day { number: 1, title: \"A\", run: run::<day1::A>, validate: validate::<day1::A>, generate: day1::A::generate, stream: day1::A::stream },\n];\n";
+        let registered = register_day(registry, 14, "Restroom Redoubt", "RestroomRedoubt").unwrap();
+        let earlier = register_day(&registered, 12, "Garden Groups", "GardenGroups").unwrap();
+        assert!(earlier.contains("stream: day1::A::stream },\n    Day { number: 12, "));
+        assert!(earlier.contains("stream: day12::GardenGroups::stream },\n    Day { number: 14, "));
+        assert!(registered.starts_with("use crate::{day1, day10, day14, day2, day9};\n"));
+        assert!(registered.contains("pub const DAYS: [Day; 3] = ["));
+        assert!(registered.contains(
+            "generate: day1::A::generate, stream: day1::A::stream },\n    Day { number: 14, title: \"Restroom Redoubt\", run: run::<day14::RestroomRedoubt>, \
+             validate: validate::<day14::RestroomRedoubt>, generate: day14::RestroomRedoubt::generate, stream: day14::RestroomRedoubt::stream },\n];"
+        ));
+    }
+
+    #[test]
+    fn scaffold_project() {
+        let root = std::env::temp_dir().join(format!("aoc2024-scaffold-{}", std::process::id()));
+        let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+        write(&root.join("src/lib.rs"), &read(&manifest.join("src/lib.rs")).unwrap()).unwrap();
+        write(&root.join("src/registry.rs"), &read(&manifest.join("src/registry.rs")).unwrap()).unwrap();
+
+        let files = scaffold(&root, 25, Some("Code Chronicle")).unwrap();
+        assert_eq!(5, files.len());
+        let module = read(&root.join("src/day25/mod.rs")).unwrap();
+        assert!(module.contains("pub struct CodeChronicle;"));
+        assert!(module.contains("Err(AocError::Input(\"Day 25 is not implemented yet\".to_string()))"));
+        assert!(!module.contains("todo!"));
+        assert!(read(&root.join("src/lib.rs")).unwrap().contains("pub mod day25;"));
+        assert!(read(&root.join("src/registry.rs")).unwrap().contains("run::<day25::CodeChronicle>"));
+        assert!(root.join("res/day25/answers").exists());
+        assert!(root.join("res/day25/examples/example.answers").exists());
+        assert!(scaffold(&root, 25, None).is_err());
+        assert!(scaffold(&root, 26, None).is_err());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
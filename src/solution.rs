@@ -0,0 +1,122 @@
+use crate::error::AocError;
+use crate::random::Rng;
+use crate::registry::Part;
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// A puzzle solution, split into parsing the input once and solving both parts from the result.
+pub trait Solution {
+    /// The parsed puzzle input, which may borrow from the raw input.
+    type Input<'a>;
+
+    /// Whether parsing does the bulk of the work, so that parsing and solving are reported as one
+    /// time rather than separately.
+    const COMBINED_TIMING: bool = false;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError>;
+
+    fn part1(input: &Self::Input<'_>) -> Answer;
+
+    fn part2(input: &Self::Input<'_>) -> Answer;
+
+    /// Solves `part` while reading the input line by line, for solutions that never need all of
+    /// it in memory. `None` if the solution has to load the whole input.
+    fn stream(_reader: &mut dyn BufRead, _part: Part) -> Option<Result<Answer, AocError>> {
+        None
+    }
+
+    /// Generates a random input in the puzzle's format, where `size` scales it, e.g. the number of
+    /// lines or the side length of a map.
+    fn generate(rng: &mut Rng, size: usize) -> String;
+}
+
+/// Puzzle answers normalized so that equal numbers compare equal regardless of the integer type
+/// a solution computed them in. Non-negative values are always stored as `Unsigned`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Text(String),
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::Unsigned(value as u64)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::Unsigned(value)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Unsigned(value as u64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        match u64::try_from(value) {
+            Ok(value) => Answer::Unsigned(value),
+            Err(_) => Answer::Signed(value),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Unsigned(value) => write!(f, "{}", value),
+            Answer::Signed(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err("Empty answer".to_string());
+        }
+        if let Ok(value) = input.parse::<u64>() {
+            return Ok(Answer::Unsigned(value));
+        }
+        if let Ok(value) = input.parse::<i64>() {
+            return Ok(Answer::Signed(value));
+        }
+        Ok(Answer::Text(input.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_integers() {
+        assert_eq!(Answer::from(42u32), Answer::from(42i64));
+        assert_eq!(Answer::from(42usize), Answer::from(42u64));
+        assert_eq!(Answer::Signed(-3), Answer::from(-3i64));
+    }
+
+    #[test]
+    fn round_trip_through_text() {
+        for answer in [Answer::Unsigned(92612386119138), Answer::Signed(-12), Answer::Text("a,b".to_string())] {
+            assert_eq!(answer, answer.to_string().parse::<Answer>().unwrap());
+        }
+        assert!("  ".parse::<Answer>().is_err());
+    }
+}
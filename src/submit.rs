@@ -0,0 +1,310 @@
+use crate::client::HttpClient;
+use crate::error::AocError;
+use crate::registry::Part;
+use crate::solution::Answer;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The server's verdict on a submitted answer.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Incorrect,
+    /// An answer was submitted too recently, with the seconds left to wait.
+    Wait(u64),
+    /// The part is already solved or not unlocked yet.
+    WrongLevel,
+    /// A response that could not be understood, with its text.
+    Unknown(String),
+}
+
+impl Verdict {
+    /// Reads the verdict from the HTML page returned for a submission.
+    pub fn parse(page: &str) -> Verdict {
+        let text = article_text(page);
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if text.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if text.contains("That's not the right answer") {
+            Verdict::Incorrect
+        } else if let Some(seconds) = wait_time(&text) {
+            Verdict::Wait(seconds)
+        } else if text.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown(text)
+        }
+    }
+
+    /// How a rejected guess is written to the log, or `None` if the verdict does not reject it.
+    fn rejection(&self) -> Option<&'static str> {
+        match self {
+            Verdict::TooHigh => Some("too high"),
+            Verdict::TooLow => Some("too low"),
+            Verdict::Incorrect => Some("incorrect"),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "wrong, too high"),
+            Verdict::TooLow => write!(f, "wrong, too low"),
+            Verdict::Incorrect => write!(f, "wrong"),
+            Verdict::Wait(seconds) => write!(f, "submitted too recently, wait {}s", seconds),
+            Verdict::WrongLevel => write!(f, "already solved or not unlocked yet"),
+            Verdict::Unknown(text) => write!(f, "unrecognized response: {}", text),
+        }
+    }
+}
+
+/// The text inside the page's `<article>`, or the whole page, without tags and extra whitespace.
+fn article_text(page: &str) -> String {
+    // The article's own tag is still open after splitting at `<article`.
+    let (article, mut in_tag) = match page.split_once("<article").and_then(|(_, rest)| rest.split_once("</article>")) {
+        Some((article, _)) => (article, true),
+        None => (page, false),
+    };
+
+    let mut text = String::new();
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Reads `You have 1m 5s left to wait` as seconds.
+fn wait_time(text: &str) -> Option<u64> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (duration, _) = rest.split_once(" left to wait")?;
+    duration.split_whitespace().try_fold(0, |seconds, component| {
+        let (value, factor) = match component.as_bytes().last()? {
+            b'h' => (&component[..component.len() - 1], 3600),
+            b'm' => (&component[..component.len() - 1], 60),
+            b's' => (&component[..component.len() - 1], 1),
+            _ => return None,
+        };
+        Some(seconds + value.parse::<u64>().ok()? * factor)
+    })
+}
+
+/// The outcome of [`submit`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Submission {
+    /// The answer was not sent, because it is known to be wrong or the server asked to wait.
+    Refused(String),
+    Sent(Verdict),
+}
+
+/// The local log of rejected guesses of a day and until when the server asked to wait, stored as
+/// lines like `part1: 1234 too high` and `wait until: <unix seconds>`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Guesses {
+    rejected: Vec<(Part, Answer, Verdict)>,
+    wait_until: Option<u64>,
+}
+
+impl Guesses {
+    /// Reads the log of a day inside an inputs directory, which is empty if nothing was submitted yet.
+    pub fn read(directory: &Path, day: u8) -> Result<Guesses, AocError> {
+        let path = guesses_path(directory, day);
+        if !path.exists() {
+            return Ok(Guesses::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|error| AocError::Input(format!("Could not read guesses {}: {}", path.display(), error)))?;
+        Guesses::from_str(&content)
+    }
+
+    pub fn write(&self, directory: &Path, day: u8) -> Result<(), AocError> {
+        let path = guesses_path(directory, day);
+        let write_error = |error: std::io::Error| AocError::Input(format!("Could not write guesses {}: {}", path.display(), error));
+        std::fs::create_dir_all(path.parent().expect("guesses are inside a day directory")).map_err(write_error)?;
+        std::fs::write(&path, self.to_string()).map_err(write_error)
+    }
+
+    /// Why `answer` should not be submitted at time `now`, if it was rejected before, lies outside
+    /// the bounds set by earlier too high or too low guesses, or the server asked to wait.
+    pub fn refusal(&self, part: Part, answer: &Answer, now: u64) -> Option<String> {
+        if let Some(wait_until) = self.wait_until.filter(|wait_until| *wait_until > now) {
+            return Some(format!("the server asked to wait another {}s", wait_until - now));
+        }
+
+        self.rejected
+            .iter()
+            .filter(|(rejected_part, _, _)| *rejected_part == part)
+            .find_map(|(_, rejected, verdict)| {
+                let bound = number(answer).zip(number(rejected));
+                match verdict {
+                    _ if rejected == answer => Some(format!("{} was already rejected as {}", answer, verdict.rejection()?)),
+                    Verdict::TooHigh if bound.is_some_and(|(answer, rejected)| answer > rejected) => Some(format!("{} is above {}, which was too high", answer, rejected)),
+                    Verdict::TooLow if bound.is_some_and(|(answer, rejected)| answer < rejected) => Some(format!("{} is below {}, which was too low", answer, rejected)),
+                    _ => None,
+                }
+            })
+    }
+
+    /// Remembers the verdict on `answer` if it rejects it or asks to wait.
+    pub fn record(&mut self, part: Part, answer: &Answer, verdict: &Verdict, now: u64) {
+        match verdict {
+            Verdict::Wait(seconds) => self.wait_until = Some(now + seconds),
+            verdict if verdict.rejection().is_some() => self.rejected.push((part, answer.clone(), verdict.clone())),
+            _ => (),
+        }
+    }
+}
+
+fn number(answer: &Answer) -> Option<i128> {
+    match answer {
+        Answer::Unsigned(value) => Some(*value as i128),
+        Answer::Signed(value) => Some(*value as i128),
+        Answer::Text(_) => None,
+    }
+}
+
+impl FromStr for Guesses {
+    type Err = AocError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut guesses = Guesses::default();
+        for line in input.lines().filter(|line| !line.trim().is_empty() && !line.starts_with('#')) {
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| AocError::at(input, line, "expected 'part1: <answer> <verdict>' or 'wait until: <seconds>'"))?;
+            let part = match key.trim() {
+                "wait until" => {
+                    guesses.wait_until = Some(value.trim().parse().map_err(|_| AocError::at(input, value, "invalid time"))?);
+                    continue;
+                }
+                "part1" => Part::One,
+                "part2" => Part::Two,
+                _ => return Err(AocError::at(input, key, format!("unknown part '{}'", key.trim()))),
+            };
+
+            let (answer, verdict) = [Verdict::TooHigh, Verdict::TooLow, Verdict::Incorrect]
+                .into_iter()
+                .find_map(|verdict| value.trim_end().strip_suffix(verdict.rejection()?).map(|answer| (answer, verdict)))
+                .ok_or_else(|| AocError::at(input, value, "expected the answer followed by 'too high', 'too low' or 'incorrect'"))?;
+            let answer = Answer::from_str(answer).map_err(|error| AocError::at(input, value, error))?;
+            guesses.rejected.push((part, answer, verdict));
+        }
+
+        Ok(guesses)
+    }
+}
+
+impl Display for Guesses {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (part, answer, verdict) in self.rejected.iter() {
+            writeln!(f, "part{}: {} {}", part.number(), answer, verdict.rejection().unwrap_or_default())?;
+        }
+        if let Some(wait_until) = self.wait_until {
+            writeln!(f, "wait until: {}", wait_until)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn guesses_path(directory: &Path, day: u8) -> PathBuf {
+    directory.join(format!("day{}", day)).join("guesses")
+}
+
+/// Posts `answer` for `part` of `day`, unless the guesses logged in `directory` show it is wrong or
+/// that it is too early to submit again. Rejections and wait times are added to the log.
+pub fn submit(client: &impl HttpClient, session: &str, day: u8, part: Part, answer: &Answer, directory: &Path) -> Result<Submission, AocError> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_secs()).unwrap_or_default();
+    let mut guesses = Guesses::read(directory, day)?;
+    if let Some(reason) = guesses.refusal(part, answer, now) {
+        return Ok(Submission::Refused(reason));
+    }
+
+    let level = part.number().to_string();
+    let answer_text = answer.to_string();
+    let response = client.post(&format!("/2024/day/{}/answer", day), session, &[("level", &level), ("answer", &answer_text)])?;
+    if response.status != 200 {
+        return Err(AocError::Http(format!("Day {} answer was not accepted ({}): {}", day, response.status, article_text(&response.body))));
+    }
+
+    let verdict = Verdict::parse(&response.body);
+    guesses.record(part, answer, &verdict, now);
+    guesses.write(directory, day)?;
+    Ok(Submission::Sent(verdict))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{mock, UreqClient};
+
+    fn page(text: &str) -> String {
+        format!("<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>", text)
+    }
+
+    #[test]
+    fn parse_verdicts() {
+        assert_eq!(Verdict::Correct, Verdict::parse(&page("That's the right answer! You are <em>one gold star</em> closer.")));
+        assert_eq!(Verdict::TooHigh, Verdict::parse(&page("That's not the right answer; your answer is too high.")));
+        assert_eq!(Verdict::TooLow, Verdict::parse(&page("That's not the right answer; your answer is too low.")));
+        assert_eq!(Verdict::Incorrect, Verdict::parse(&page("That's not the right answer. If you're stuck, ...")));
+        assert_eq!(Verdict::Wait(65), Verdict::parse(&page("You gave an answer too recently. You have 1m 5s left to wait.")));
+        assert_eq!(Verdict::WrongLevel, Verdict::parse(&page("You don't seem to be solving the right level.")));
+        assert_eq!(Verdict::Unknown("Something else".to_string()), Verdict::parse(&page("Something <b>else</b>")));
+    }
+
+    #[test]
+    fn refuse_known_wrong_guesses() {
+        let mut guesses = Guesses::default();
+        guesses.record(Part::One, &Answer::Unsigned(100), &Verdict::TooHigh, 0);
+        guesses.record(Part::One, &Answer::Unsigned(10), &Verdict::TooLow, 0);
+        guesses.record(Part::Two, &Answer::Text("abc".to_string()), &Verdict::Incorrect, 0);
+
+        assert!(guesses.refusal(Part::One, &Answer::Unsigned(100), 0).unwrap().contains("already rejected as too high"));
+        assert!(guesses.refusal(Part::One, &Answer::Unsigned(150), 0).unwrap().contains("above 100"));
+        assert!(guesses.refusal(Part::One, &Answer::Signed(-1), 0).unwrap().contains("below 10"));
+        assert!(guesses.refusal(Part::Two, &Answer::Text("abc".to_string()), 0).is_some());
+        assert_eq!(None, guesses.refusal(Part::One, &Answer::Unsigned(50), 0));
+        assert_eq!(None, guesses.refusal(Part::Two, &Answer::Unsigned(150), 0));
+
+        guesses.record(Part::One, &Answer::Unsigned(50), &Verdict::Wait(30), 1000);
+        assert!(guesses.refusal(Part::One, &Answer::Unsigned(50), 1010).unwrap().contains("20s"));
+        assert_eq!(None, guesses.refusal(Part::One, &Answer::Unsigned(50), 1030));
+    }
+
+    #[test]
+    fn round_trip_log() {
+        let log = "part1: 100 too high\npart2: a b incorrect\nwait until: 1734000000\n";
+        let guesses = Guesses::from_str(log).unwrap();
+        assert_eq!(log, guesses.to_string());
+        assert_eq!(Some(AocError::parse(1, 7, "expected the answer followed by 'too high', 'too low' or 'incorrect'")), Guesses::from_str("part1: 100").err());
+    }
+
+    #[test]
+    fn submit_and_log_rejections() {
+        let directory = std::env::temp_dir().join(format!("aoc2024-submit-{}", std::process::id()));
+        let (url, server) = mock::serve(vec![(200, "<article><p>That's not the right answer; your answer is too low.</p></article>")]);
+        let client = UreqClient::new(url);
+
+        assert_eq!(Ok(Submission::Sent(Verdict::TooLow)), submit(&client, "secret", 2, Part::Two, &Answer::Unsigned(7), &directory));
+        assert!(matches!(submit(&client, "secret", 2, Part::Two, &Answer::Unsigned(6), &directory), Ok(Submission::Refused(_))));
+        assert!(server.join().unwrap()[0].ends_with("level=2&answer=7"));
+        assert_eq!("part2: 7 too low\n", std::fs::read_to_string(guesses_path(&directory, 2)).unwrap());
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,148 @@
+use crate::error::AocError;
+use crate::input::InputSource;
+use crate::registry::{Day, Part};
+use crate::solution::Answer;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// The expected answers of a day, read from lines like `part1: 1234`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Answers {
+    /// Reads the recorded answers of a day inside an inputs directory, which are empty if none were recorded.
+    pub fn read(directory: &Path, day: u8) -> Result<Answers, AocError> {
+        let path = answers_path(directory, day);
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|error| AocError::Input(format!("Could not read answers {}: {}", path.display(), error)))?;
+        Answers::from_str(&content)
+    }
+
+    pub fn part(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+}
+
+impl FromStr for Answers {
+    type Err = AocError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut answers = Answers::default();
+        for line in input.lines().filter(|line| !line.trim().is_empty() && !line.starts_with('#')) {
+            let (part, answer) = line
+                .split_once(':')
+                .ok_or_else(|| AocError::at(input, line, "expected 'part1: <answer>' or 'part2: <answer>'"))?;
+            let answer = Answer::from_str(answer).map_err(|error| AocError::at(input, answer, error))?;
+            match part.trim() {
+                "part1" => answers.part1 = Some(answer),
+                "part2" => answers.part2 = Some(answer),
+                _ => return Err(AocError::at(input, part, format!("unknown part '{}'", part.trim()))),
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+pub fn answers_path(directory: &Path, day: u8) -> PathBuf {
+    directory.join(format!("day{}", day)).join("answers")
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail { expected: Answer },
+    /// No answer has been recorded for this part yet.
+    Missing,
+    /// The input could not be loaded or parsed.
+    Error(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Verification {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Option<Answer>,
+    pub status: Status,
+    /// The time spent parsing and solving, if the part could be solved.
+    pub time: Option<Duration>,
+}
+
+/// Solves `part` of `day` from the input in `directory` and compares it with the answer recorded there.
+pub fn verify(day: &Day, part: Part, directory: &Path) -> Verification {
+    let verification = |answer, status, time| Verification { day: day.number, part, answer, status, time };
+
+    let expected = match Answers::read(directory, day.number) {
+        Ok(answers) => answers.part(part).cloned(),
+        Err(error) => return verification(None, Status::Error(error.to_string()), None),
+    };
+    let input = InputSource::Directory(directory.to_path_buf()).load(day.number);
+    let run = match input.and_then(|input| day.run(&input, part)) {
+        Ok(run) => run,
+        Err(error) => return verification(None, Status::Error(error.to_string()), None),
+    };
+    let (time, answer) = (run.total_time(), run.answer);
+
+    let status = match expected {
+        Some(expected) if expected == answer => Status::Pass,
+        Some(expected) => Status::Fail { expected },
+        None => Status::Missing,
+    };
+    verification(Some(answer), status, Some(time))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::DEFAULT_INPUTS_DIR;
+    use crate::registry;
+
+    #[test]
+    fn parse_answers() {
+        let answers = Answers::from_str("# day 1\npart1: 11\n\npart2: abc\n").unwrap();
+        assert_eq!(Some(&Answer::Unsigned(11)), answers.part(Part::One));
+        assert_eq!(Some(&Answer::Text("abc".to_string())), answers.part(Part::Two));
+        assert_eq!(Answers { part1: None, part2: Some(Answer::Signed(-2)) }, Answers::from_str("part2: -2").unwrap());
+        assert_eq!(Err(AocError::parse(2, 1, "unknown part 'part3'")), Answers::from_str("part1: 1\npart3: 2"));
+        assert!(Answers::from_str("part1 1").is_err());
+        assert!(Answers::from_str("part1:").is_err());
+    }
+
+    #[test]
+    fn verify_recorded_answer() {
+        let verification = verify(registry::find(11).unwrap(), Part::One, Path::new(DEFAULT_INPUTS_DIR));
+        assert_eq!(Status::Pass, verification.status);
+        assert_eq!(Answers::read(Path::new(DEFAULT_INPUTS_DIR), 11).unwrap().part1, verification.answer);
+    }
+
+    #[test]
+    fn verify_statuses() {
+        let directory = std::env::temp_dir().join(format!("aoc2024-verify-{}", std::process::id()));
+        let write = |day: u8, file: &str, content: &str| {
+            std::fs::create_dir_all(directory.join(format!("day{}", day))).unwrap();
+            std::fs::write(directory.join(format!("day{}", day)).join(file), content).unwrap();
+        };
+        write(11, "part1", "125 17");
+        write(11, "answers", "part1: 55312\n");
+        write(2, "part1", "1 2 3");
+        write(2, "answers", "part1: 0\n");
+        write(3, "answers", "part1: 0\n");
+
+        assert_eq!(Status::Pass, verify(registry::find(11).unwrap(), Part::One, &directory).status);
+        assert_eq!(Status::Missing, verify(registry::find(11).unwrap(), Part::Two, &directory).status);
+        assert_eq!(Status::Fail { expected: Answer::Unsigned(0) }, verify(registry::find(2).unwrap(), Part::One, &directory).status);
+        assert!(matches!(verify(registry::find(3).unwrap(), Part::One, &directory).status, Status::Error(_)));
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,83 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+/// Runs the binary with `arguments`, piping `input` to its stdin.
+fn aoc2024(arguments: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc2024"))
+        .args(arguments)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+const LOCATIONS: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+#[test]
+fn run_both_parts_from_stdin() {
+    let output = aoc2024(&["run", "--day", "1", "--input", "-"], LOCATIONS);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(
+        "Day 1 part 1 (Historian Hysteria): 11\nDay 1 part 2 (Historian Hysteria): 31\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn run_parts_on_threads_from_stdin() {
+    let output = aoc2024(&["run", "--day", "1", "--input", "-", "--jobs", "2", "--format", "csv"], LOCATIONS);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let answers = String::from_utf8_lossy(&output.stdout).lines().skip(1).map(|line| line.split(',').nth(3).unwrap().to_string()).collect::<Vec<_>>();
+    assert_eq!(vec!["11", "31"], answers);
+}
+
+#[test]
+fn bench_both_parts_from_stdin() {
+    let output = aoc2024(&["bench", "--day", "1", "--input", "-", "--runs", "2", "--format", "csv"], LOCATIONS);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let answers = String::from_utf8_lossy(&output.stdout).lines().skip(1).map(|line| line.split(',').nth(2).unwrap().to_string()).collect::<Vec<_>>();
+    assert_eq!(vec!["11", "31"], answers);
+}
+
+#[test]
+fn stream_a_part_from_stdin() {
+    let output = aoc2024(&["run", "--day", "1", "--part", "2", "--input", "-", "--stream"], LOCATIONS);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!("Day 1 part 2 (Historian Hysteria): 31\n", String::from_utf8_lossy(&output.stdout));
+
+    let output = aoc2024(&["run", "--day", "3", "--part", "1", "--input", "-", "--stream"], "mul(2,3)");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Day 3 cannot be streamed"));
+}
+
+#[test]
+fn validate_rejects_inputs_the_solver_cannot_handle() {
+    let output = aoc2024(&["validate", "--day", "6", "-"], ".#.\n#^#\n.#.\n");
+    assert!(!output.status.success());
+    assert_eq!(
+        "Day 6: line 2, column 2: the guard is boxed in by obstructions\n2 | #^#\n  |  ^\n",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = aoc2024(&["validate", "--day", "6", "-"], ".#.\n#^.\n.#.\n");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let output = aoc2024(&["validate", "--day", "5", "-"], "1|2\n2|1\n\n1,2,3\n");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("Day 5: line 4, column 1: the ordering rules for this update contain a cycle"));
+}
+
+#[test]
+fn bench_reports_every_part_that_fails() {
+    let output = aoc2024(&["bench", "--day", "1", "--input", "-", "--runs", "1", "--format", "csv"], "3   x\n");
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    assert!(lines.next().unwrap().ends_with(",status,message"));
+    let rows = lines.collect::<Vec<_>>();
+    assert_eq!(2, rows.len());
+    assert!(rows.iter().all(|row| row.contains(",failed,") && row.contains("invalid")), "{:?}", rows);
+}